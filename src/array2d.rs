@@ -1,4 +1,28 @@
-#[derive(Clone)]
+use std::fmt;
+use std::iter::{Enumerate, StepBy, Take};
+use std::mem;
+use std::ops::{Index, IndexMut, Range};
+use std::slice::{self, Chunks, ChunksMut};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+mod export;
+mod graph;
+mod image;
+mod mask;
+mod noise;
+mod numeric;
+#[cfg(feature = "serde")]
+pub mod serde_rle;
+
+pub use self::graph::{Connectivity, DistanceMetric};
+pub use self::image::FitMode;
+pub use self::mask::MorphOp;
+
+use crate::{DimMismatch, LenMismatch, OutOfBounds};
+
+#[derive(Clone, PartialEq, Eq)]
 pub struct Array2D<T: Clone> {
     width: usize,
     height: usize,
@@ -15,6 +39,31 @@ impl Coord2D {
     pub fn new(x: usize, y: usize) -> Self {
         Coord2D { x, y }
     }
+
+    pub fn to_f64(self) -> (f64, f64) {
+        (self.x as f64, self.y as f64)
+    }
+
+    pub fn from_f64_round(x: f64, y: f64) -> Option<Self> {
+        Self::from_f64_with(x, y, f64::round)
+    }
+
+    pub fn from_f64_floor(x: f64, y: f64) -> Option<Self> {
+        Self::from_f64_with(x, y, f64::floor)
+    }
+
+    pub fn from_f64_ceil(x: f64, y: f64) -> Option<Self> {
+        Self::from_f64_with(x, y, f64::ceil)
+    }
+
+    fn from_f64_with<F: Fn(f64) -> f64>(x: f64, y: f64, op: F) -> Option<Self> {
+        let (x, y) = (op(x), op(y));
+        if x >= 0.0 && y >= 0.0 && x < usize::MAX as f64 && y < usize::MAX as f64 {
+            Some(Coord2D::new(x as usize, y as usize))
+        } else {
+            None
+        }
+    }
 }
 
 impl From<(usize, usize)> for Coord2D {
@@ -29,6 +78,96 @@ impl From<[usize; 2]> for Coord2D {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct CoordRange2D {
+    pub x: Range<usize>,
+    pub y: Range<usize>,
+}
+
+impl CoordRange2D {
+    pub fn new(x: Range<usize>, y: Range<usize>) -> Self {
+        CoordRange2D { x, y }
+    }
+
+    pub fn contains(&self, coord: Coord2D) -> bool {
+        self.x.contains(&coord.x) && self.y.contains(&coord.y)
+    }
+
+    pub fn iter(&self) -> CoordRangeIter {
+        CoordRangeIter {
+            x: self.x.clone(),
+            y: self.y.clone(),
+            coord: Coord2D::new(self.x.start, self.y.start),
+        }
+    }
+}
+
+impl IntoIterator for CoordRange2D {
+    type Item = Coord2D;
+    type IntoIter = CoordRangeIter;
+
+    fn into_iter(self) -> CoordRangeIter {
+        self.iter()
+    }
+}
+
+pub struct CoordRangeIter {
+    x: Range<usize>,
+    y: Range<usize>,
+    coord: Coord2D,
+}
+
+impl Iterator for CoordRangeIter {
+    type Item = Coord2D;
+
+    fn next(&mut self) -> Option<Coord2D> {
+        if self.x.is_empty() || self.coord.y >= self.y.end {
+            return None;
+        }
+
+        let next = self.coord;
+        self.coord.x += 1;
+        if self.coord.x >= self.x.end {
+            self.coord.x = self.x.start;
+            self.coord.y += 1;
+        }
+        Some(next)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum MirrorAxis {
+    Horizontal,
+    Vertical,
+    Both,
+}
+
+#[derive(Clone)]
+pub struct GridSnapshot<T: Clone> {
+    width: usize,
+    height: usize,
+    data: Vec<T>,
+}
+
 pub struct Iter<'a, T: 'a + Clone> {
     this: &'a Array2D<T>,
     coord: Coord2D,
@@ -77,6 +216,158 @@ impl<'a, T: Clone> Iterator for IterMut<'a, T> {
     }
 }
 
+pub struct DiagIter<'a, T: 'a + Clone> {
+    this: &'a Array2D<T>,
+    coord: Option<Coord2D>,
+    anti: bool,
+}
+
+impl<'a, T: Clone> Iterator for DiagIter<'a, T> {
+    type Item = (Coord2D, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let coord = self.coord?;
+        self.coord = self.this.diag_step(coord, self.anti);
+        Some((coord, self.this.at(coord)))
+    }
+}
+
+pub struct DiagIterMut<'a, T: 'a + Clone> {
+    inner: Enumerate<Take<StepBy<slice::IterMut<'a, T>>>>,
+    start: Coord2D,
+    anti: bool,
+}
+
+impl<'a, T: Clone> Iterator for DiagIterMut<'a, T> {
+    type Item = (Coord2D, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (i, value) = self.inner.next()?;
+        let x = if self.anti {
+            self.start.x - i
+        } else {
+            self.start.x + i
+        };
+        Some((Coord2D::new(x, self.start.y + i), value))
+    }
+}
+
+pub struct ColIter<'a, T: 'a> {
+    inner: StepBy<slice::Iter<'a, T>>,
+}
+
+impl<'a, T> Iterator for ColIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+pub struct ColIterMut<'a, T: 'a> {
+    inner: StepBy<slice::IterMut<'a, T>>,
+}
+
+impl<'a, T> Iterator for ColIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+pub struct RegionIter<'a, T: 'a + Clone> {
+    rest: &'a [T],
+    stride: usize,
+    x: Range<usize>,
+    rows: Range<usize>,
+    row: slice::Iter<'a, T>,
+    coord: Coord2D,
+}
+
+impl<'a, T: Clone> Iterator for RegionIter<'a, T> {
+    type Item = (Coord2D, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(value) = self.row.next() {
+                let coord = self.coord;
+                self.coord.x += 1;
+                return Some((coord, value));
+            }
+
+            let y = self.rows.next()?;
+            let (row, rest) = self.rest.split_at(self.stride);
+            self.rest = rest;
+            self.row = row[self.x.clone()].iter();
+            self.coord = Coord2D::new(self.x.start, y);
+        }
+    }
+}
+
+pub struct RegionIterMut<'a, T: 'a + Clone> {
+    rest: &'a mut [T],
+    stride: usize,
+    x: Range<usize>,
+    rows: Range<usize>,
+    row: slice::IterMut<'a, T>,
+    coord: Coord2D,
+}
+
+impl<'a, T: Clone> Iterator for RegionIterMut<'a, T> {
+    type Item = (Coord2D, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(value) = self.row.next() {
+                let coord = self.coord;
+                self.coord.x += 1;
+                return Some((coord, value));
+            }
+
+            let y = self.rows.next()?;
+            let (row, rest) = mem::take(&mut self.rest).split_at_mut(self.stride);
+            self.rest = rest;
+            self.row = row[self.x.clone()].iter_mut();
+            self.coord = Coord2D::new(self.x.start, y);
+        }
+    }
+}
+
+pub struct Zip3Iter<'a, T: 'a + Clone, U: 'a + Clone, V: 'a + Clone> {
+    a: &'a Array2D<T>,
+    b: &'a Array2D<U>,
+    c: &'a Array2D<V>,
+    index: usize,
+}
+
+impl<'a, T: Clone, U: Clone, V: Clone> Iterator for Zip3Iter<'a, T, U, V> {
+    type Item = (Coord2D, &'a T, &'a U, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.index;
+        if index >= self.a.data.len() {
+            return None;
+        }
+
+        self.index += 1;
+        Some((
+            self.a.index_coord(index),
+            &self.a.data[index],
+            &self.b.data[index],
+            &self.c.data[index],
+        ))
+    }
+}
+
 impl<T: Clone + Default> Array2D<T> {
     pub fn new(width: usize, height: usize) -> Self {
         Self::new_with(width, height, T::default())
@@ -94,6 +385,54 @@ impl<T: Clone> Array2D<T> {
         }
     }
 
+    pub(crate) fn from_raw(width: usize, height: usize, data: Vec<T>) -> Self {
+        debug_assert!(data.len() == width * height);
+        Array2D {
+            width,
+            height,
+            data,
+        }
+    }
+
+    pub fn from_parts(width: usize, height: usize, data: Vec<T>) -> Result<Self, LenMismatch> {
+        if data.len() != width * height {
+            return Err(LenMismatch {
+                expected: width * height,
+                actual: data.len(),
+            });
+        }
+        Ok(Self::from_raw(width, height, data))
+    }
+
+    /// Wraps a row-major vector without copying, failing if its length is not
+    /// `width * height`. Same as `from_parts`.
+    pub fn from_data(width: usize, height: usize, data: Vec<T>) -> Result<Self, LenMismatch> {
+        Self::from_parts(width, height, data)
+    }
+
+    pub fn into_parts(self) -> (usize, usize, Vec<T>) {
+        (self.width, self.height, self.data)
+    }
+
+    pub fn into_data(self) -> Vec<T> {
+        self.data
+    }
+
+    pub fn outer_product<A, B, F>(rows: &[A], cols: &[B], f: F) -> Self
+    where
+        F: Fn(&A, &B) -> T,
+    {
+        let mut data: Vec<T> = Vec::with_capacity(rows.len() * cols.len());
+        for row in rows {
+            data.extend(cols.iter().map(|col| f(row, col)));
+        }
+        Array2D {
+            width: cols.len(),
+            height: rows.len(),
+            data,
+        }
+    }
+
     pub fn width(&self) -> usize {
         self.width
     }
@@ -110,20 +449,120 @@ impl<T: Clone> Array2D<T> {
         coord.x + self.width * coord.y
     }
 
-    pub fn iter(&self) -> Iter<T> {
+    fn index_coord(&self, index: usize) -> Coord2D {
+        Coord2D::new(index % self.width, index / self.width)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             this: self,
             coord: Coord2D::new(0, 0),
         }
     }
 
-    pub fn iter_mut(&mut self) -> IterMut<T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         IterMut {
             this: self,
             coord: Coord2D::new(0, 0),
         }
     }
 
+    pub fn iter_with_index(&self) -> impl Iterator<Item = (usize, Coord2D, &T)> {
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(index, value)| (index, self.index_coord(index), value))
+    }
+
+    pub fn map<U: Clone, F: FnMut(&T) -> U>(&self, f: F) -> Array2D<U> {
+        Array2D {
+            width: self.width,
+            height: self.height,
+            data: self.data.iter().map(f).collect(),
+        }
+    }
+
+    pub fn map_with_coord<U: Clone, F: FnMut(Coord2D, &T) -> U>(&self, mut f: F) -> Array2D<U> {
+        let data = self.iter().map(|(coord, value)| f(coord, value)).collect();
+        Array2D {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+
+    pub fn map_inplace<F: FnMut(Coord2D, &mut T)>(&mut self, mut f: F) {
+        for (coord, value) in self.iter_mut() {
+            f(coord, value);
+        }
+    }
+
+    pub fn scan_diagonal<C: Into<Coord2D>>(&self, start: C) -> DiagIter<'_, T> {
+        DiagIter {
+            coord: Some(self.valid_coord(start)),
+            this: self,
+            anti: false,
+        }
+    }
+
+    pub fn scan_anti_diagonal<C: Into<Coord2D>>(&self, start: C) -> DiagIter<'_, T> {
+        DiagIter {
+            coord: Some(self.valid_coord(start)),
+            this: self,
+            anti: true,
+        }
+    }
+
+    pub fn scan_diagonal_mut<C: Into<Coord2D>>(&mut self, start: C) -> DiagIterMut<'_, T> {
+        self.diag_iter_mut(start.into(), false)
+    }
+
+    pub fn scan_anti_diagonal_mut<C: Into<Coord2D>>(&mut self, start: C) -> DiagIterMut<'_, T> {
+        self.diag_iter_mut(start.into(), true)
+    }
+
+    fn diag_iter_mut(&mut self, start: Coord2D, anti: bool) -> DiagIterMut<'_, T> {
+        let start = self.valid_coord(start);
+        let (len, step) = if anti {
+            ((start.x + 1).min(self.height - start.y), self.width - 1)
+        } else {
+            (
+                (self.width - start.x).min(self.height - start.y),
+                self.width + 1,
+            )
+        };
+        let index = self.coord_index(start);
+        DiagIterMut {
+            inner: self.data[index..]
+                .iter_mut()
+                .step_by(step.max(1))
+                .take(len)
+                .enumerate(),
+            start,
+            anti,
+        }
+    }
+
+    fn valid_coord<C: Into<Coord2D>>(&self, coord: C) -> Coord2D {
+        let coord = coord.into();
+        assert!(self.coord_is_valid(coord));
+        coord
+    }
+
+    fn diag_step(&self, coord: Coord2D, anti: bool) -> Option<Coord2D> {
+        let x = if anti {
+            coord.x.checked_sub(1)?
+        } else {
+            coord.x + 1
+        };
+        let next = Coord2D::new(x, coord.y + 1);
+        if self.coord_is_valid(next) {
+            Some(next)
+        } else {
+            None
+        }
+    }
+
     pub fn data(&self) -> &[T] {
         &self.data
     }
@@ -132,17 +571,51 @@ impl<T: Clone> Array2D<T> {
         &mut self.data
     }
 
-    pub fn at<C: Into<Coord2D>>(&self, coord: C) -> &T {
+    pub fn byte_len(&self) -> usize {
+        self.data.len() * mem::size_of::<T>()
+    }
+
+    pub fn get<C: Into<Coord2D>>(&self, coord: C) -> Option<&T> {
         let coord = coord.into();
-        assert!(self.coord_is_valid(coord));
-        &self.data[self.coord_index(coord)]
+        if self.coord_is_valid(coord) {
+            Some(&self.data[self.coord_index(coord)])
+        } else {
+            None
+        }
     }
 
-    pub fn at_mut<C: Into<Coord2D>>(&mut self, coord: C) -> &mut T {
+    pub fn get_mut<C: Into<Coord2D>>(&mut self, coord: C) -> Option<&mut T> {
         let coord = coord.into();
-        assert!(self.coord_is_valid(coord));
-        let index = self.coord_index(coord);
-        &mut self.data[index]
+        if self.coord_is_valid(coord) {
+            let index = self.coord_index(coord);
+            Some(&mut self.data[index])
+        } else {
+            None
+        }
+    }
+
+    pub fn at<C: Into<Coord2D>>(&self, coord: C) -> &T {
+        self.get(coord).expect("coordinate out of bounds")
+    }
+
+    pub fn at_mut<C: Into<Coord2D>>(&mut self, coord: C) -> &mut T {
+        self.get_mut(coord).expect("coordinate out of bounds")
+    }
+
+    pub fn at_xy(&self, x: usize, y: usize) -> &T {
+        self.at(Coord2D::new(x, y))
+    }
+
+    pub fn at_xy_mut(&mut self, x: usize, y: usize) -> &mut T {
+        self.at_mut(Coord2D::new(x, y))
+    }
+
+    pub fn get_xy(&self, x: usize, y: usize) -> Option<&T> {
+        self.get(Coord2D::new(x, y))
+    }
+
+    pub fn get_xy_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.get_mut(Coord2D::new(x, y))
     }
 
     pub fn set<C: Into<Coord2D>>(&mut self, coord: C, value: T) {
@@ -186,4 +659,969 @@ impl<T: Clone> Array2D<T> {
             dst.clone_from_slice(src);
         }
     }
+
+    pub fn pop_row(&mut self) -> Option<Vec<T>> {
+        if self.height == 0 {
+            return None;
+        }
+
+        self.height -= 1;
+        Some(self.data.split_off(self.width * self.height))
+    }
+
+    pub fn rotate_cw_in_place(&mut self) {
+        self.rotate_square_in_place(true);
+    }
+
+    pub fn rotate_ccw_in_place(&mut self) {
+        self.rotate_square_in_place(false);
+    }
+
+    fn rotate_square_in_place(&mut self, clockwise: bool) {
+        assert!(self.width == self.height);
+
+        let n = self.width;
+        for first in 0..n / 2 {
+            let last = n - 1 - first;
+            for i in first..last {
+                let offset = i - first;
+                let top = self.coord_index(Coord2D::new(i, first));
+                let right = self.coord_index(Coord2D::new(last, i));
+                let bottom = self.coord_index(Coord2D::new(last - offset, last));
+                let left = self.coord_index(Coord2D::new(first, last - offset));
+
+                if clockwise {
+                    self.data.swap(top, right);
+                    self.data.swap(top, bottom);
+                    self.data.swap(top, left);
+                } else {
+                    self.data.swap(top, left);
+                    self.data.swap(top, bottom);
+                    self.data.swap(top, right);
+                }
+            }
+        }
+    }
+
+    pub fn insert_row(&mut self, y: usize, values: &[T]) -> Result<(), LenMismatch> {
+        assert!(y <= self.height);
+        if values.len() != self.width {
+            return Err(LenMismatch {
+                expected: self.width,
+                actual: values.len(),
+            });
+        }
+
+        let index = self.width * y;
+        self.data.splice(index..index, values.iter().cloned());
+        self.height += 1;
+        Ok(())
+    }
+
+    pub fn diag(&self) -> Vec<&T> {
+        let n = self.width.min(self.height);
+        (0..n).map(|i| self.at((i, i))).collect()
+    }
+
+    pub fn diag_as_vec(&self) -> Vec<T> {
+        self.diag().into_iter().cloned().collect()
+    }
+
+    pub fn set_diag(&mut self, values: &[T]) {
+        assert!(values.len() == self.width.min(self.height));
+        for (i, value) in values.iter().enumerate() {
+            self.set((i, i), value.clone());
+        }
+    }
+
+    pub fn anti_diag(&self) -> Vec<&T> {
+        let n = self.width.min(self.height);
+        (0..n).map(|i| self.at((self.width - 1 - i, i))).collect()
+    }
+
+    pub fn set_anti_diag(&mut self, values: &[T]) {
+        assert!(values.len() == self.width.min(self.height));
+        for (i, value) in values.iter().enumerate() {
+            let x = self.width - 1 - i;
+            self.set((x, i), value.clone());
+        }
+    }
+
+    pub fn dedup_rows(&self) -> Self
+    where
+        T: PartialEq,
+    {
+        let mut data: Vec<T> = Vec::new();
+        let mut height = 0;
+        let mut last: Option<&[T]> = None;
+        for y in 0..self.height {
+            let row = &self.data[self.width * y..self.width * (y + 1)];
+            if last != Some(row) {
+                data.extend_from_slice(row);
+                height += 1;
+                last = Some(row);
+            }
+        }
+
+        Array2D {
+            width: self.width,
+            height,
+            data,
+        }
+    }
+
+    pub fn row_period(&self) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        let width = self.width;
+        (1..self.height).find(|&p| {
+            let shifted = &self.data[width * p..];
+            self.data[..shifted.len()] == *shifted
+        })
+    }
+
+    pub fn col_period(&self) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        (1..self.width).find(|&p| {
+            (0..self.height).all(|y| {
+                let row = &self.data[self.width * y..self.width * (y + 1)];
+                row[..self.width - p] == row[p..]
+            })
+        })
+    }
+
+    pub fn rotate_90_n(&self, n: i32) -> Self {
+        match n.rem_euclid(4) {
+            1 => self.rotated(|this, x, y| this.at((y, this.height - 1 - x))),
+            2 => {
+                let mut data = self.data.clone();
+                data.reverse();
+                Array2D {
+                    width: self.width,
+                    height: self.height,
+                    data,
+                }
+            }
+            3 => self.rotated(|this, x, y| this.at((this.width - 1 - y, x))),
+            _ => self.clone(),
+        }
+    }
+
+    pub fn transpose(&self) -> Self {
+        self.rotated(|this, x, y| this.at((y, x)))
+    }
+
+    /// Returns the array rotated by 90 degrees clockwise.
+    pub fn rotate90(&self) -> Self {
+        self.rotate_90_n(1)
+    }
+
+    pub fn rotate180(&self) -> Self {
+        self.rotate_90_n(2)
+    }
+
+    pub fn rotate270(&self) -> Self {
+        self.rotate_90_n(3)
+    }
+
+    /// Mirrors the array left to right.
+    pub fn flip_horizontal(&mut self) {
+        self.mirror_in_place(MirrorAxis::Horizontal);
+    }
+
+    /// Mirrors the array top to bottom.
+    pub fn flip_vertical(&mut self) {
+        self.mirror_in_place(MirrorAxis::Vertical);
+    }
+
+    pub fn rotate_90_in_place_buffered(&mut self, buf: &mut Vec<T>) {
+        mem::swap(&mut self.data, buf);
+        self.data.clear();
+        for y in 0..self.width {
+            for x in 0..self.height {
+                let source = Coord2D::new(y, self.height - 1 - x);
+                self.data.push(buf[self.coord_index(source)].clone());
+            }
+        }
+        mem::swap(&mut self.width, &mut self.height);
+    }
+
+    fn rotated<'a, F: Fn(&'a Self, usize, usize) -> &'a T>(&'a self, source: F) -> Self {
+        let mut data: Vec<T> = Vec::with_capacity(self.data.len());
+        for y in 0..self.width {
+            for x in 0..self.height {
+                data.push(source(self, x, y).clone());
+            }
+        }
+        Array2D {
+            width: self.height,
+            height: self.width,
+            data,
+        }
+    }
+
+    pub fn mirror_in_place(&mut self, axis: MirrorAxis) {
+        match axis {
+            MirrorAxis::Horizontal => {
+                for row in self.data.chunks_mut(self.width.max(1)) {
+                    row.reverse();
+                }
+            }
+            MirrorAxis::Vertical => {
+                for y in 0..self.height / 2 {
+                    let (top, bottom) = self.data.split_at_mut(self.width * (self.height - 1 - y));
+                    top[self.width * y..self.width * (y + 1)]
+                        .swap_with_slice(&mut bottom[..self.width]);
+                }
+            }
+            MirrorAxis::Both => self.data.reverse(),
+        }
+    }
+
+    pub fn resize_with<F: FnMut(Coord2D) -> T>(&mut self, width: usize, height: usize, mut f: F) {
+        let mut old = mem::take(&mut self.data).into_iter();
+        let mut data: Vec<T> = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                if x < self.width && y < self.height {
+                    data.push(old.next().unwrap());
+                } else {
+                    data.push(f(Coord2D::new(x, y)));
+                }
+            }
+            if y < self.height {
+                for _ in width..self.width {
+                    old.next();
+                }
+            }
+        }
+
+        self.width = width;
+        self.height = height;
+        self.data = data;
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        self.row_range(y, 0..self.width)
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        let width = self.width;
+        self.row_range_mut(y, 0..width)
+    }
+
+    pub fn col(&self, x: usize) -> ColIter<'_, T> {
+        assert!(x < self.width);
+        let start = x.min(self.data.len());
+        ColIter {
+            inner: self.data[start..].iter().step_by(self.width),
+        }
+    }
+
+    pub fn col_mut(&mut self, x: usize) -> ColIterMut<'_, T> {
+        assert!(x < self.width);
+        let start = x.min(self.data.len());
+        ColIterMut {
+            inner: self.data[start..].iter_mut().step_by(self.width),
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = ColIter<'_, T>> {
+        (0..self.width).map(move |x| self.col(x))
+    }
+
+    pub fn row_range(&self, y: usize, x_range: Range<usize>) -> &[T] {
+        let range = self.row_range_indices(y, x_range);
+        &self.data[range]
+    }
+
+    pub fn row_range_mut(&mut self, y: usize, x_range: Range<usize>) -> &mut [T] {
+        let range = self.row_range_indices(y, x_range);
+        &mut self.data[range]
+    }
+
+    fn row_range_indices(&self, y: usize, x_range: Range<usize>) -> Range<usize> {
+        assert!(y < self.height);
+        assert!(x_range.start <= x_range.end);
+        assert!(x_range.end <= self.width);
+
+        let row_begin = self.width * y;
+        row_begin + x_range.start..row_begin + x_range.end
+    }
+
+    pub fn rows_slice(&self, y_start: usize, y_end: usize) -> &[T] {
+        assert!(y_start <= y_end);
+        assert!(y_end <= self.height);
+        &self.data[self.width * y_start..self.width * y_end]
+    }
+
+    pub fn rows_slice_mut(&mut self, y_start: usize, y_end: usize) -> &mut [T] {
+        assert!(y_start <= y_end);
+        assert!(y_end <= self.height);
+        &mut self.data[self.width * y_start..self.width * y_end]
+    }
+
+    pub fn map_rows<U, F>(&self, mut f: F) -> Result<Array2D<U>, LenMismatch>
+    where
+        U: Clone,
+        F: FnMut(usize, &[T]) -> Vec<U>,
+    {
+        let mut width = None;
+        let mut data: Vec<U> = Vec::new();
+        for y in 0..self.height {
+            let row = f(y, self.rows_slice(y, y + 1));
+            let expected = *width.get_or_insert(row.len());
+            if row.len() != expected {
+                return Err(LenMismatch {
+                    expected,
+                    actual: row.len(),
+                });
+            }
+            data.extend(row);
+        }
+
+        Ok(Array2D {
+            width: width.unwrap_or(0),
+            height: self.height,
+            data,
+        })
+    }
+
+    pub fn select_rows(&self, row_indices: &[usize]) -> Self {
+        let mut data: Vec<T> = Vec::with_capacity(self.width * row_indices.len());
+        for &y in row_indices {
+            data.extend_from_slice(self.rows_slice(y, y + 1));
+        }
+        Array2D {
+            width: self.width,
+            height: row_indices.len(),
+            data,
+        }
+    }
+
+    pub fn select_cols(&self, col_indices: &[usize]) -> Self {
+        assert!(col_indices.iter().all(|&x| x < self.width));
+
+        let mut data: Vec<T> = Vec::with_capacity(col_indices.len() * self.height);
+        for y in 0..self.height {
+            let row = self.rows_slice(y, y + 1);
+            data.extend(col_indices.iter().map(|&x| row[x].clone()));
+        }
+        Array2D {
+            width: col_indices.len(),
+            height: self.height,
+            data,
+        }
+    }
+
+    /// Places equally high arrays side by side. An empty slice yields a `0x0` array.
+    pub fn hstack(arrays: &[Self]) -> Result<Self, DimMismatch> {
+        let height = arrays.first().map_or(0, |array| array.height);
+        for array in arrays {
+            if array.height != height {
+                return Err(DimMismatch {
+                    expected: (array.width, height),
+                    actual: (array.width, array.height),
+                });
+            }
+        }
+
+        let width = arrays.iter().map(|array| array.width).sum();
+        let mut data: Vec<T> = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for array in arrays {
+                data.extend_from_slice(array.rows_slice(y, y + 1));
+            }
+        }
+        Ok(Array2D {
+            width,
+            height,
+            data,
+        })
+    }
+
+    /// Stacks equally wide arrays from top to bottom. An empty slice yields a `0x0` array.
+    pub fn vstack(arrays: &[Self]) -> Result<Self, DimMismatch> {
+        let width = arrays.first().map_or(0, |array| array.width);
+        for array in arrays {
+            if array.width != width {
+                return Err(DimMismatch {
+                    expected: (width, array.height),
+                    actual: (array.width, array.height),
+                });
+            }
+        }
+
+        let height = arrays.iter().map(|array| array.height).sum();
+        let mut data: Vec<T> = Vec::with_capacity(width * height);
+        for array in arrays {
+            data.extend_from_slice(&array.data);
+        }
+        Ok(Array2D {
+            width,
+            height,
+            data,
+        })
+    }
+
+    pub fn stack_along_x(arrays: Vec<Self>) -> Result<Self, DimMismatch> {
+        let height = arrays.first().map_or(0, |array| array.height);
+        let width = arrays.iter().map(|array| array.width).sum();
+        let mut rows = Vec::with_capacity(arrays.len());
+        for array in arrays {
+            if array.height != height {
+                return Err(DimMismatch {
+                    expected: (array.width, height),
+                    actual: (array.width, array.height),
+                });
+            }
+            rows.push((array.width, array.data.into_iter()));
+        }
+
+        let mut data: Vec<T> = Vec::with_capacity(width * height);
+        for _ in 0..height {
+            for (row_width, values) in rows.iter_mut() {
+                data.extend(values.take(*row_width));
+            }
+        }
+        Ok(Array2D {
+            width,
+            height,
+            data,
+        })
+    }
+
+    pub fn stack_along_y(arrays: Vec<Self>) -> Result<Self, DimMismatch> {
+        let width = arrays.first().map_or(0, |array| array.width);
+        let height = arrays.iter().map(|array| array.height).sum();
+        let mut data: Vec<T> = Vec::with_capacity(width * height);
+        for mut array in arrays {
+            if array.width != width {
+                return Err(DimMismatch {
+                    expected: (width, array.height),
+                    actual: (array.width, array.height),
+                });
+            }
+            data.append(&mut array.data);
+        }
+        Ok(Array2D {
+            width,
+            height,
+            data,
+        })
+    }
+
+    pub fn split_grid(&self, cols: usize, rows: usize) -> Result<Vec<Self>, DimMismatch> {
+        assert!(cols > 0);
+        assert!(rows > 0);
+        if self.width % cols != 0 || self.height % rows != 0 {
+            return Err(DimMismatch {
+                expected: (cols * (self.width / cols), rows * (self.height / rows)),
+                actual: (self.width, self.height),
+            });
+        }
+
+        let tile_width = self.width / cols;
+        let tile_height = self.height / rows;
+        let mut tiles = Vec::with_capacity(cols * rows);
+        for row in 0..rows {
+            for col in 0..cols {
+                let mut data = Vec::with_capacity(tile_width * tile_height);
+                for y in row * tile_height..(row + 1) * tile_height {
+                    let x_range = col * tile_width..(col + 1) * tile_width;
+                    data.extend_from_slice(self.row_range(y, x_range));
+                }
+                tiles.push(Self::from_raw(tile_width, tile_height, data));
+            }
+        }
+        Ok(tiles)
+    }
+
+    pub fn from_diagonal(diag: &[T], off_diag: T) -> Self {
+        let mut array = Self::new_with(diag.len(), diag.len(), off_diag);
+        array.set_diag(diag);
+        array
+    }
+
+    pub fn from_bidiagonal(diag: &[T], super_diag: &[T], off_diag: T) -> Self {
+        assert!(super_diag.len() == diag.len().saturating_sub(1));
+
+        let mut array = Self::from_diagonal(diag, off_diag);
+        for (i, value) in super_diag.iter().enumerate() {
+            array.set((i + 1, i), value.clone());
+        }
+        array
+    }
+
+    pub fn from_tridiagonal(sub_diag: &[T], diag: &[T], super_diag: &[T], off_diag: T) -> Self {
+        assert!(sub_diag.len() == diag.len().saturating_sub(1));
+
+        let mut array = Self::from_bidiagonal(diag, super_diag, off_diag);
+        for (i, value) in sub_diag.iter().enumerate() {
+            array.set((i, i + 1), value.clone());
+        }
+        array
+    }
+
+    pub fn chunk_rows(&self, chunk_size: usize) -> Chunks<'_, T> {
+        assert!(chunk_size > 0);
+        self.data.chunks((chunk_size * self.width).max(1))
+    }
+
+    pub fn chunk_rows_mut(&mut self, chunk_size: usize) -> ChunksMut<'_, T> {
+        assert!(chunk_size > 0);
+        self.data.chunks_mut((chunk_size * self.width).max(1))
+    }
+
+    pub fn column_chunks(&self, chunk_size: usize) -> impl Iterator<Item = Self> + '_ {
+        assert!(chunk_size > 0);
+        (0..self.width).step_by(chunk_size).map(move |x| {
+            let width = chunk_size.min(self.width - x);
+            let mut data: Vec<T> = Vec::with_capacity(width * self.height);
+            for y in 0..self.height {
+                data.extend_from_slice(self.row_range(y, x..x + width));
+            }
+            Array2D {
+                width,
+                height: self.height,
+                data,
+            }
+        })
+    }
+
+    pub fn min_bounding_rect(&self, background: &T) -> Option<Rect>
+    where
+        T: PartialEq,
+    {
+        let mut coords = self
+            .iter()
+            .filter(|&(_, value)| value != background)
+            .map(|(coord, _)| coord);
+        let first = coords.next()?;
+        let (min, max) = coords.fold((first, first), |(min, max), coord| {
+            (
+                Coord2D::new(min.x.min(coord.x), min.y.min(coord.y)),
+                Coord2D::new(max.x.max(coord.x), max.y.max(coord.y)),
+            )
+        });
+        Some(Rect::new(
+            min.x,
+            min.y,
+            max.x - min.x + 1,
+            max.y - min.y + 1,
+        ))
+    }
+
+    pub fn iter_non_default<'a>(
+        &'a self,
+        background: &'a T,
+    ) -> impl Iterator<Item = (Coord2D, &'a T)> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter().filter(move |&(_, value)| value != background)
+    }
+
+    pub fn count_non_default(&self, background: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.data
+            .iter()
+            .filter(|&value| value != background)
+            .count()
+    }
+
+    pub fn snapshot(&self) -> GridSnapshot<T> {
+        GridSnapshot {
+            width: self.width,
+            height: self.height,
+            data: self.data.clone(),
+        }
+    }
+
+    pub fn restore(&mut self, snap: &GridSnapshot<T>) {
+        assert!(self.width == snap.width);
+        assert!(self.height == snap.height);
+        self.data.clone_from_slice(&snap.data);
+    }
+
+    /// Iterates over the `width x height` window starting at `origin`, yielding absolute
+    /// coordinates. The window is clamped to the array, so cells beyond the right or
+    /// bottom edge are silently skipped.
+    pub fn iter_region<C: Into<Coord2D>>(
+        &self,
+        origin: C,
+        width: usize,
+        height: usize,
+    ) -> RegionIter<'_, T> {
+        let (x, rows) = self.clamp_region(origin.into(), width, height);
+        RegionIter {
+            rest: &self.data[self.width * rows.start..],
+            stride: self.width,
+            x,
+            rows,
+            row: [].iter(),
+            coord: Coord2D::new(0, 0),
+        }
+    }
+
+    pub fn iter_region_mut<C: Into<Coord2D>>(
+        &mut self,
+        origin: C,
+        width: usize,
+        height: usize,
+    ) -> RegionIterMut<'_, T> {
+        let (x, rows) = self.clamp_region(origin.into(), width, height);
+        RegionIterMut {
+            rest: &mut self.data[self.width * rows.start..],
+            stride: self.width,
+            x,
+            rows,
+            row: [].iter_mut(),
+            coord: Coord2D::new(0, 0),
+        }
+    }
+
+    fn clamp_region(
+        &self,
+        origin: Coord2D,
+        width: usize,
+        height: usize,
+    ) -> (Range<usize>, Range<usize>) {
+        let x_end = origin.x.saturating_add(width).min(self.width);
+        let y_end = origin.y.saturating_add(height).min(self.height);
+        (origin.x.min(x_end)..x_end, origin.y.min(y_end)..y_end)
+    }
+
+    pub fn iter_coord_range(&self, range: CoordRange2D) -> impl Iterator<Item = (Coord2D, &T)> {
+        assert!(range.x.end <= self.width);
+        assert!(range.y.end <= self.height);
+        range
+            .into_iter()
+            .map(move |coord| (coord, &self.data[self.coord_index(coord)]))
+    }
+
+    pub fn apply_patch(&mut self, patch: &[(Coord2D, T)]) {
+        for (coord, value) in patch {
+            self.set(*coord, value.clone());
+        }
+    }
+
+    pub fn try_apply_patch(&mut self, patch: &[(Coord2D, T)]) -> Result<(), OutOfBounds> {
+        if let Some(&(coord, _)) = patch.iter().find(|(coord, _)| !self.coord_is_valid(*coord)) {
+            return Err(OutOfBounds { coord });
+        }
+        self.apply_patch(patch);
+        Ok(())
+    }
+
+    pub fn transform_coords<F>(
+        &self,
+        new_width: usize,
+        new_height: usize,
+        backward_map: F,
+        fill: T,
+    ) -> Self
+    where
+        F: Fn(Coord2D) -> Option<Coord2D>,
+    {
+        let mut data: Vec<T> = Vec::with_capacity(new_width * new_height);
+        for y in 0..new_height {
+            for x in 0..new_width {
+                let value = match backward_map(Coord2D::new(x, y)) {
+                    Some(source) if self.coord_is_valid(source) => self.at(source),
+                    _ => &fill,
+                };
+                data.push(value.clone());
+            }
+        }
+        Array2D {
+            width: new_width,
+            height: new_height,
+            data,
+        }
+    }
+
+    pub fn zip3<'a, U: Clone, V: Clone>(
+        &'a self,
+        b: &'a Array2D<U>,
+        c: &'a Array2D<V>,
+    ) -> Zip3Iter<'a, T, U, V> {
+        assert!(self.width == b.width && self.height == b.height);
+        assert!(self.width == c.width && self.height == c.height);
+        Zip3Iter {
+            a: self,
+            b,
+            c,
+            index: 0,
+        }
+    }
+
+    pub fn zip3_with<U, V, W, F>(&self, b: &Array2D<U>, c: &Array2D<V>, f: F) -> Array2D<W>
+    where
+        U: Clone,
+        V: Clone,
+        W: Clone,
+        F: Fn(&T, &U, &V) -> W,
+    {
+        let data = self.zip3(b, c).map(|(_, a, b, c)| f(a, b, c)).collect();
+        Array2D {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+}
+
+/// Indexes cells by anything convertible into `Coord2D`, panicking like `at` when out of
+/// bounds. There is deliberately no `Index<usize>` for linear access, so a tuple, array or
+/// `Coord2D` index always addresses a cell by its coordinates.
+impl<T: Clone, C: Into<Coord2D>> Index<C> for Array2D<T> {
+    type Output = T;
+
+    fn index(&self, coord: C) -> &T {
+        self.at(coord)
+    }
+}
+
+impl<T: Clone, C: Into<Coord2D>> IndexMut<C> for Array2D<T> {
+    fn index_mut(&mut self, coord: C) -> &mut T {
+        self.at_mut(coord)
+    }
+}
+
+impl<'a, T: Clone> IntoIterator for &'a Array2D<T> {
+    type Item = (Coord2D, &'a T);
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T: Clone> IntoIterator for &'a mut Array2D<T> {
+    type Item = (Coord2D, &'a mut T);
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T: Clone + fmt::Debug> fmt::Debug for Array2D<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows: Vec<&[T]> = self.data.chunks(self.width.max(1)).collect();
+        f.debug_struct("Array2D")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("rows", &rows)
+            .finish()
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+impl<T: Clone + bytemuck::Pod> Array2D<T> {
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::cast_slice(&self.data)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T: Clone + Sync> Array2D<T> {
+    pub fn par_map_indexed<U, F>(&self, f: F) -> Array2D<U>
+    where
+        U: Clone + Send,
+        F: Fn(Coord2D, &T) -> U + Sync,
+    {
+        let data = self
+            .data
+            .par_iter()
+            .enumerate()
+            .map(|(index, value)| f(self.index_coord(index), value))
+            .collect();
+
+        Array2D {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbered(width: usize, height: usize) -> Array2D<usize> {
+        Array2D::from_data(width, height, (0..width * height).collect()).unwrap()
+    }
+
+    #[test]
+    fn get_returns_corners() {
+        let array = numbered(4, 3);
+        assert_eq!(array.get((0, 0)), Some(&0));
+        assert_eq!(array.get((3, 0)), Some(&3));
+        assert_eq!(array.get((0, 2)), Some(&8));
+        assert_eq!(array.get((3, 2)), Some(&11));
+    }
+
+    #[test]
+    fn get_rejects_exactly_out_of_bounds() {
+        let mut array = numbered(4, 3);
+        assert_eq!(array.get((4, 0)), None);
+        assert_eq!(array.get((0, 3)), None);
+        assert_eq!(array.get((4, 3)), None);
+        assert_eq!(array.get_mut((4, 2)), None);
+        assert_eq!(array.get_mut((3, 3)), None);
+    }
+
+    #[test]
+    fn get_on_zero_sized() {
+        let mut array: Array2D<usize> = Array2D::new(0, 0);
+        assert_eq!(array.get((0, 0)), None);
+        assert_eq!(array.get_mut((0, 0)), None);
+
+        let mut array: Array2D<usize> = Array2D::new(3, 0);
+        assert_eq!(array.get((0, 0)), None);
+        assert_eq!(array.get_mut((2, 0)), None);
+    }
+
+    #[test]
+    fn get_mut_writes_cell() {
+        let mut array = numbered(4, 3);
+        *array.get_mut((3, 2)).unwrap() = 100;
+        assert_eq!(array.at((3, 2)), &100);
+    }
+
+    #[test]
+    fn get_rejects_out_of_bounds_per_axis() {
+        let array = numbered(4, 3);
+        assert_eq!(array.get((5, 0)), None);
+        assert_eq!(array.get((usize::MAX, 0)), None);
+        assert_eq!(array.get((0, 4)), None);
+        assert_eq!(array.get((0, usize::MAX)), None);
+    }
+
+    #[test]
+    fn get_mut_reaches_first_and_last_cell() {
+        let mut array = numbered(4, 3);
+        *array.get_mut((0, 0)).unwrap() += 100;
+        *array.get_mut((3, 2)).unwrap() += 100;
+        assert_eq!(array.data()[0], 100);
+        assert_eq!(array.data()[11], 111);
+    }
+
+    #[test]
+    fn map_changes_element_type() {
+        let heights = numbered(3, 2);
+        let mask = heights.map(|&height| height >= 3);
+        assert_eq!((mask.width(), mask.height()), (3, 2));
+        assert_eq!(mask.data(), &[false, false, false, true, true, true]);
+    }
+
+    #[test]
+    fn map_with_coord_passes_coordinates() {
+        let array = numbered(3, 2);
+        let mapped = array.map_with_coord(|coord, &value| (coord.x, coord.y, value));
+        assert_eq!((mapped.width(), mapped.height()), (3, 2));
+        assert_eq!(mapped.at((2, 1)), &(2, 1, 5));
+        assert_eq!(mapped.at((0, 1)), &(0, 1, 3));
+    }
+
+    #[test]
+    fn cols_of_zero_height_array_are_empty() {
+        let mut array: Array2D<u8> = Array2D::new(3, 0);
+        assert_eq!(array.cols().count(), 3);
+        assert!(array.cols().all(|mut col| col.next().is_none()));
+        assert_eq!(array.col(2).count(), 0);
+        assert_eq!(array.col_mut(2).count(), 0);
+
+        let mut array: Array2D<u8> = Array2D::new(3, 1);
+        array.pop_row();
+        assert_eq!(array.col(2).count(), 0);
+    }
+
+    #[test]
+    fn rotate90_four_times_is_identity() {
+        for &(width, height) in &[(3, 2), (2, 3), (4, 4), (1, 5), (5, 1)] {
+            let array = numbered(width, height);
+            let rotated = array.rotate90().rotate90().rotate90().rotate90();
+            assert!(rotated == array);
+            assert!(array.rotate90().rotate270() == array);
+            assert!(array.rotate180().rotate180() == array);
+        }
+    }
+
+    #[test]
+    fn rotations_of_non_square_arrays() {
+        let array = numbered(3, 2);
+        assert_eq!(array.transpose().data(), &[0, 3, 1, 4, 2, 5]);
+        assert_eq!(array.rotate90().data(), &[3, 0, 4, 1, 5, 2]);
+        assert_eq!(array.rotate180().data(), &[5, 4, 3, 2, 1, 0]);
+        assert_eq!(array.rotate270().data(), &[2, 5, 1, 4, 0, 3]);
+        assert_eq!(
+            (array.rotate90().width(), array.rotate90().height()),
+            (2, 3)
+        );
+    }
+
+    #[test]
+    fn flips_mirror_rows_and_columns() {
+        let mut array = numbered(3, 2);
+        array.flip_horizontal();
+        assert_eq!(array.data(), &[2, 1, 0, 5, 4, 3]);
+
+        let mut array = numbered(3, 2);
+        array.flip_vertical();
+        assert_eq!(array.data(), &[3, 4, 5, 0, 1, 2]);
+    }
+
+    #[test]
+    fn reorienting_strips_and_empty_arrays_does_not_panic() {
+        for &(width, height) in &[(1, 4), (4, 1), (0, 0), (0, 3), (3, 0)] {
+            let mut array = numbered(width, height);
+            let transposed = array.transpose();
+            assert_eq!((transposed.width(), transposed.height()), (height, width));
+            let rotated = array.rotate90();
+            assert_eq!((rotated.width(), rotated.height()), (height, width));
+            assert!(array.rotate180() == array.rotate90().rotate90());
+            assert!(array.rotate270() == array.rotate90().rotate180());
+
+            let original = array.clone();
+            array.flip_horizontal();
+            array.flip_vertical();
+            assert!(array == original.rotate180());
+        }
+    }
+
+    #[test]
+    fn split_grid_into_tiles() {
+        let tiles = numbered(4, 2).split_grid(2, 2).unwrap();
+        assert_eq!(tiles.len(), 4);
+        assert_eq!(tiles[0].data(), &[0, 1]);
+        assert_eq!(tiles[1].data(), &[2, 3]);
+        assert_eq!(tiles[2].data(), &[4, 5]);
+        assert_eq!(tiles[3].data(), &[6, 7]);
+    }
+
+    #[test]
+    fn split_grid_of_empty_arrays() {
+        let tiles = numbered(0, 0).split_grid(1, 1).unwrap();
+        assert_eq!(tiles.len(), 1);
+        assert_eq!((tiles[0].width(), tiles[0].height()), (0, 0));
+
+        let tiles = numbered(4, 0).split_grid(2, 1).unwrap();
+        assert_eq!(tiles.len(), 2);
+        assert!(tiles
+            .iter()
+            .all(|tile| tile.width() == 2 && tile.height() == 0));
+    }
 }
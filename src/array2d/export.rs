@@ -0,0 +1,88 @@
+use std::io::{self, Write};
+
+use crate::{Array2D, Coord2D};
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl<T: Clone> Array2D<T> {
+    /// Writes the array as an undirected Graphviz graph, with one node named `"x_y"` per
+    /// cell and edges between 4-connected neighbors.
+    pub fn to_dot<W: Write, F: Fn(Coord2D, &T) -> String>(
+        &self,
+        mut writer: W,
+        label: F,
+    ) -> io::Result<()> {
+        writeln!(writer, "graph {{")?;
+        writeln!(writer, "    rankdir=TB;")?;
+        for (coord, value) in self.iter() {
+            writeln!(
+                writer,
+                "    \"{}_{}\" [label=\"{}\"];",
+                coord.x,
+                coord.y,
+                escape(&label(coord, value))
+            )?;
+        }
+        for (coord, _) in self.iter() {
+            if coord.x + 1 < self.width {
+                writeln!(
+                    writer,
+                    "    \"{}_{}\" -- \"{}_{}\";",
+                    coord.x,
+                    coord.y,
+                    coord.x + 1,
+                    coord.y
+                )?;
+            }
+            if coord.y + 1 < self.height {
+                writeln!(
+                    writer,
+                    "    \"{}_{}\" -- \"{}_{}\";",
+                    coord.x,
+                    coord.y,
+                    coord.x,
+                    coord.y + 1
+                )?;
+            }
+        }
+        writeln!(writer, "}}")
+    }
+
+    /// Writes the array as an SVG 1.1 image with one `cell_size`-sized square per cell,
+    /// filled with the CSS color returned by `fill_color`.
+    pub fn to_svg<W: Write, F: Fn(Coord2D, &T) -> &str>(
+        &self,
+        mut writer: W,
+        cell_size: usize,
+        fill_color: F,
+    ) -> io::Result<()> {
+        writeln!(writer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(
+            writer,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">",
+            self.width * cell_size,
+            self.height * cell_size
+        )?;
+        for (coord, value) in self.iter() {
+            writeln!(
+                writer,
+                "  <rect x=\"{}\" y=\"{}\" width=\"{2}\" height=\"{2}\" fill=\"{3}\"/>",
+                coord.x * cell_size,
+                coord.y * cell_size,
+                cell_size,
+                escape_xml(fill_color(coord, value))
+            )?;
+        }
+        writeln!(writer, "</svg>")
+    }
+}
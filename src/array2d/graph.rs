@@ -0,0 +1,559 @@
+use std::collections::VecDeque;
+
+use crate::{Array2D, Coord2D};
+
+const NEIGHBORS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const NEIGHBORS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+impl Connectivity {
+    fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Connectivity::Four => &NEIGHBORS_4,
+            Connectivity::Eight => &NEIGHBORS_8,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DistanceMetric {
+    Euclidean,
+    Manhattan,
+}
+
+fn closer<I: Copy + Ord>(a: Option<(u64, I)>, b: Option<(u64, I)>) -> Option<(u64, I)> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+/// Labels every cell with the id of its nearest seed. The distances are computed exactly
+/// in two separable passes: the nearest seed within every column first, then the nearest
+/// of those column candidates along every row. Ties go to the smaller id, and seeds
+/// outside the grid are ignored.
+fn nearest_seed_labels<I: Copy + Ord>(
+    width: usize,
+    height: usize,
+    seeds: &[(Coord2D, I)],
+    metric: DistanceMetric,
+) -> Array2D<I> {
+    let mut seed_ids: Vec<Option<I>> = vec![None; width * height];
+    for &(coord, id) in seeds {
+        if coord.x < width && coord.y < height {
+            let cell = &mut seed_ids[coord.x + width * coord.y];
+            *cell = closer(cell.map(|other| (0, other)), Some((0, id))).map(|(_, id)| id);
+        }
+    }
+    assert!(
+        seed_ids.iter().any(Option::is_some) || width * height == 0,
+        "no seed inside the grid"
+    );
+
+    // Nearest seed within the same column as `(|dy|, id)`, from above and from below.
+    let mut columns: Vec<Option<(u64, I)>> = vec![None; width * height];
+    for x in 0..width {
+        let mut above = None;
+        for y in 0..height {
+            if let Some(id) = seed_ids[x + width * y] {
+                above = Some((y, id));
+            }
+            columns[x + width * y] = above.map(|(seed_y, id)| ((y - seed_y) as u64, id));
+        }
+        let mut below = None;
+        for y in (0..height).rev() {
+            if let Some(id) = seed_ids[x + width * y] {
+                below = Some((y, id));
+            }
+            let candidate = below.map(|(seed_y, id)| ((seed_y - y) as u64, id));
+            columns[x + width * y] = closer(columns[x + width * y], candidate);
+        }
+    }
+
+    let mut labels = Vec::with_capacity(width * height);
+    for row in columns.chunks(width.max(1)) {
+        match metric {
+            DistanceMetric::Euclidean => nearest_in_row_euclidean(row, &mut labels),
+            DistanceMetric::Manhattan => nearest_in_row_manhattan(row, &mut labels),
+        }
+    }
+    Array2D::from_raw(width, height, labels)
+}
+
+/// Picks for every cell the column candidate minimizing `|dx| + |dy|`, by sweeping the
+/// row once in each direction.
+fn nearest_in_row_manhattan<I: Copy + Ord>(row: &[Option<(u64, I)>], labels: &mut Vec<I>) {
+    let mut nearest = Vec::with_capacity(row.len());
+    let mut left = None;
+    for &candidate in row {
+        left = closer(left.map(|(distance, id)| (distance + 1, id)), candidate);
+        nearest.push(left);
+    }
+    let mut right = None;
+    for (x, &candidate) in row.iter().enumerate().rev() {
+        right = closer(right.map(|(distance, id)| (distance + 1, id)), candidate);
+        nearest[x] = closer(nearest[x], right);
+    }
+    labels.extend(nearest.into_iter().map(|cell| cell.unwrap().1));
+}
+
+/// Picks for every cell the column candidate minimizing `dx² + dy²`, using the lower
+/// envelope of the parabolas `(x - column)² + dy²`. The envelope boundaries are kept as
+/// exact fractions, so that cells lying exactly on a boundary can compare every tied
+/// candidate by id.
+fn nearest_in_row_euclidean<I: Copy + Ord>(row: &[Option<(u64, I)>], labels: &mut Vec<I>) {
+    let intersection = |(x0, g0, _): (i128, i128, I), (x1, g1, _): (i128, i128, I)| {
+        ((g1 + x1 * x1) - (g0 + x0 * x0), 2 * (x1 - x0))
+    };
+    let before = |(n0, d0): (i128, i128), (n1, d1): (i128, i128)| n0 * d1 < n1 * d0;
+
+    let mut envelope: Vec<(i128, i128, I)> = Vec::new();
+    let mut starts: Vec<(i128, i128)> = Vec::new();
+    for (x, &candidate) in row.iter().enumerate() {
+        if let Some((dy, id)) = candidate {
+            let parabola = (x as i128, (dy * dy) as i128, id);
+            while let Some(&last) = envelope.last() {
+                let start = intersection(last, parabola);
+                if envelope.len() > 1 && before(start, starts[starts.len() - 1]) {
+                    envelope.pop();
+                    starts.pop();
+                } else {
+                    starts.push(start);
+                    break;
+                }
+            }
+            if envelope.is_empty() {
+                starts.push((0, 1));
+            }
+            envelope.push(parabola);
+        }
+    }
+
+    let mut k = 0;
+    for x in 0..row.len() as i128 {
+        let reaches = |(numerator, denominator): (i128, i128)| numerator <= x * denominator;
+        while k + 1 < envelope.len() && reaches(starts[k + 1]) {
+            k += 1;
+        }
+        let key = |(column, g, id): (i128, i128, I)| ((x - column) * (x - column) + g, id);
+        let mut nearest = key(envelope[k]);
+        let mut j = k;
+        while j > 0 && starts[j].0 == x * starts[j].1 {
+            j -= 1;
+            nearest = nearest.min(key(envelope[j]));
+        }
+        labels.push(nearest.1);
+    }
+}
+
+impl<T: Clone> Array2D<T> {
+    fn neighbor(&self, coord: Coord2D, offset: (isize, isize)) -> Option<Coord2D> {
+        let x = coord.x as isize + offset.0;
+        let y = coord.y as isize + offset.1;
+        if x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height {
+            Some(Coord2D::new(x as usize, y as usize))
+        } else {
+            None
+        }
+    }
+
+    fn flood_from<P: Fn(&T) -> bool>(
+        &self,
+        start: Coord2D,
+        connectivity: Connectivity,
+        visited: &mut [bool],
+        predicate: P,
+    ) -> Vec<Coord2D> {
+        let mut region = Vec::new();
+        let mut queue = VecDeque::new();
+        visited[self.coord_index(start)] = true;
+        queue.push_back(start);
+
+        while let Some(coord) = queue.pop_front() {
+            region.push(coord);
+            for offset in connectivity.offsets() {
+                if let Some(next) = self.neighbor(coord, *offset) {
+                    let index = self.coord_index(next);
+                    if !visited[index] && predicate(&self.data[index]) {
+                        visited[index] = true;
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        region
+    }
+
+    /// Updates every cell from its previous value and its previous north, east, south
+    /// and west neighbors (in that order), as in a synchronous cellular automaton.
+    pub fn apply_with_neighbors_mut<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(Coord2D, &T, [Option<&T>; 4]) -> T,
+    {
+        let mut scratch = self.clone();
+        self.apply_ca_with_scratch(&mut scratch, f)
+    }
+
+    /// Same as `apply_with_neighbors_mut`, but keeps the previous state in `scratch`
+    /// so that its allocation can be reused across steps.
+    pub fn apply_ca_with_scratch<F>(&mut self, scratch: &mut Array2D<T>, f: F) -> &mut Self
+    where
+        F: Fn(Coord2D, &T, [Option<&T>; 4]) -> T,
+    {
+        scratch.width = self.width;
+        scratch.height = self.height;
+        scratch.data.clone_from(&self.data);
+
+        for index in 0..self.data.len() {
+            let coord = self.index_coord(index);
+            let mut neighbors = [None; 4];
+            for (neighbor, offset) in neighbors.iter_mut().zip(NEIGHBORS_4.iter()) {
+                *neighbor = scratch
+                    .neighbor(coord, *offset)
+                    .map(|next| scratch.at(next));
+            }
+            self.data[index] = f(coord, &scratch.data[index], neighbors);
+        }
+        self
+    }
+
+    pub fn flood_region<C: Into<Coord2D>>(&self, start: C) -> Vec<Coord2D>
+    where
+        T: PartialEq,
+    {
+        let start = start.into();
+        let value = self.at(start);
+        let mut visited = vec![false; self.data.len()];
+        self.flood_from(start, Connectivity::Four, &mut visited, |other| {
+            other == value
+        })
+    }
+
+    /// Labels the 4-connected regions of equal values with ids from `0`, in row-major
+    /// order of their first cell, and returns the labels along with the region count.
+    pub fn connected_components(&self) -> (Array2D<usize>, usize)
+    where
+        T: PartialEq,
+    {
+        self.label_components(Connectivity::Four)
+    }
+
+    /// Like `connected_components`, but regions also connect through diagonal neighbors.
+    pub fn connected_components_8(&self) -> (Array2D<usize>, usize)
+    where
+        T: PartialEq,
+    {
+        self.label_components(Connectivity::Eight)
+    }
+
+    /// Returns a mask of the largest 4-connected region holding the most common value
+    /// other than `T::default()`, which is treated as background.
+    pub fn largest_component(&self) -> Option<Array2D<bool>>
+    where
+        T: PartialEq + Default,
+    {
+        let background = T::default();
+        let mut counts: Vec<(&T, usize)> = Vec::new();
+        for value in self.data.iter().filter(|&value| *value != background) {
+            match counts.iter_mut().find(|(other, _)| *other == value) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((value, 1)),
+            }
+        }
+
+        let mut most_common = None;
+        let mut max = 0;
+        for (value, count) in counts {
+            if count > max {
+                most_common = Some(value);
+                max = count;
+            }
+        }
+        most_common.and_then(|value| self.largest_component_with_value(value))
+    }
+
+    /// Returns a mask of the largest 4-connected region of cells equal to `value`, or
+    /// `None` if no cell holds it.
+    pub fn largest_component_with_value(&self, value: &T) -> Option<Array2D<bool>>
+    where
+        T: PartialEq,
+    {
+        let (labels, count) = self.connected_components();
+        let mut sizes = vec![0usize; count];
+        for (label, cell) in labels.data.iter().zip(self.data.iter()) {
+            if cell == value {
+                sizes[*label] += 1;
+            }
+        }
+
+        let mut largest = None;
+        let mut max = 0;
+        for (label, &size) in sizes.iter().enumerate() {
+            if size > max {
+                largest = Some(label);
+                max = size;
+            }
+        }
+        largest.map(|largest| {
+            let data = labels.data.iter().map(|&label| label == largest).collect();
+            Array2D::from_raw(self.width, self.height, data)
+        })
+    }
+
+    fn label_components(&self, connectivity: Connectivity) -> (Array2D<usize>, usize)
+    where
+        T: PartialEq,
+    {
+        let mut labels = Array2D::new_with(self.width, self.height, 0);
+        let mut visited = vec![false; self.data.len()];
+        let mut count = 0;
+        for index in 0..self.data.len() {
+            if !visited[index] {
+                let value = &self.data[index];
+                let start = self.index_coord(index);
+                for coord in
+                    self.flood_from(start, connectivity, &mut visited, |other| other == value)
+                {
+                    labels.set(coord, count);
+                }
+                count += 1;
+            }
+        }
+        (labels, count)
+    }
+}
+
+impl Array2D<bool> {
+    pub fn bfs_distances(&self, start: Coord2D) -> Array2D<Option<u32>> {
+        self.bfs_distances_with(start, Connectivity::Four)
+    }
+
+    /// Returns the number of steps from `start` to every cell reachable through `true`
+    /// cells, or `None` for impassable and unreachable cells.
+    pub fn bfs_distances_with(
+        &self,
+        start: Coord2D,
+        connectivity: Connectivity,
+    ) -> Array2D<Option<u32>> {
+        let mut distances = Array2D::new_with(self.width, self.height, None);
+        if !*self.at(start) {
+            return distances;
+        }
+
+        let mut queue = VecDeque::new();
+        distances.set(start, Some(0));
+        queue.push_back((start, 0));
+        while let Some((coord, distance)) = queue.pop_front() {
+            for offset in connectivity.offsets() {
+                if let Some(next) = self.neighbor(coord, *offset) {
+                    if *self.at(next) && distances.at(next).is_none() {
+                        distances.set(next, Some(distance + 1));
+                        queue.push_back((next, distance + 1));
+                    }
+                }
+            }
+        }
+        distances
+    }
+
+    /// Counts the cell edges separating `true` cells from `false` cells or from the
+    /// outside of the array.
+    pub fn perimeter_length(&self) -> usize {
+        let mut length = 0;
+        for (coord, &value) in self.iter() {
+            if value {
+                length += NEIGHBORS_4
+                    .iter()
+                    .filter(|&&offset| {
+                        !self
+                            .neighbor(coord, offset)
+                            .is_some_and(|next| *self.at(next))
+                    })
+                    .count();
+            }
+        }
+        length
+    }
+
+    pub fn count_regions(&self, connectivity: Connectivity) -> usize {
+        let mut visited = vec![false; self.data.len()];
+        let mut count = 0;
+        for index in 0..self.data.len() {
+            if self.data[index] && !visited[index] {
+                let start = self.index_coord(index);
+                self.flood_from(start, connectivity, &mut visited, |&value| value);
+                count += 1;
+            }
+        }
+        count
+    }
+}
+
+impl Array2D<u32> {
+    /// Labels every cell with the id of its nearest seed under the given metric, in time
+    /// linear in the number of cells regardless of the number of seeds. Ties go to the
+    /// smaller id and seeds outside the grid are ignored. Panics if the grid is not empty
+    /// but no seed lies inside it.
+    pub fn voronoi(
+        width: usize,
+        height: usize,
+        seeds: &[(Coord2D, u32)],
+        metric: DistanceMetric,
+    ) -> Array2D<u32> {
+        nearest_seed_labels(width, height, seeds, metric)
+    }
+}
+
+impl Array2D<usize> {
+    /// Labels every cell with the id of the seed closest to it by Euclidean distance, in
+    /// time linear in the number of cells regardless of the number of seeds. Ties go to
+    /// the smaller id and seeds outside the grid are ignored. Panics if the grid is not
+    /// empty but no seed lies inside it.
+    pub fn voronoi_diagram(
+        width: usize,
+        height: usize,
+        seeds: &[(Coord2D, usize)],
+    ) -> Array2D<usize> {
+        nearest_seed_labels(width, height, seeds, DistanceMetric::Euclidean)
+    }
+}
+
+impl Array2D<Option<Coord2D>> {
+    /// Walks the predecessor grid back from `goal` until reaching a cell without
+    /// predecessor, and returns the path from that start cell to `goal`.
+    pub fn reconstruct_path(came_from: &Array2D<Option<Coord2D>>, goal: Coord2D) -> Vec<Coord2D> {
+        let mut path = vec![goal];
+        let mut current = goal;
+        while let Some(previous) = *came_from.at(current) {
+            assert!(
+                path.len() <= came_from.data.len(),
+                "cycle in predecessor grid"
+            );
+            path.push(previous);
+            current = previous;
+        }
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn voronoi_diagram_on_fixture() {
+        let seeds = [(Coord2D::new(0, 0), 2), (Coord2D::new(4, 2), 1)];
+        let labels = Array2D::voronoi_diagram(5, 3, &seeds);
+        #[rustfmt::skip]
+        let expected = [
+            2, 2, 2, 1, 1,
+            2, 2, 1, 1, 1,
+            2, 2, 1, 1, 1,
+        ];
+        assert_eq!(labels.data(), &expected);
+    }
+
+    #[test]
+    fn voronoi_diagram_picks_nearest_seed() {
+        let seeds = [
+            (Coord2D::new(17, 2), 0),
+            (Coord2D::new(3, 28), 1),
+            (Coord2D::new(8, 12), 2),
+        ];
+        let labels = Array2D::voronoi_diagram(40, 40, &seeds);
+        assert_eq!(labels.at((38, 30)), &2);
+    }
+
+    #[test]
+    fn voronoi_diagram_breaks_ties_by_smaller_id() {
+        let seeds = [(Coord2D::new(4, 0), 3), (Coord2D::new(0, 0), 7)];
+        let labels = Array2D::voronoi_diagram(5, 1, &seeds);
+        assert_eq!(labels.data(), &[7, 7, 3, 3, 3]);
+    }
+
+    #[test]
+    fn voronoi_diagram_ignores_seeds_outside() {
+        let seeds = [(Coord2D::new(9, 9), 0), (Coord2D::new(1, 1), 5)];
+        let labels = Array2D::voronoi_diagram(3, 3, &seeds);
+        assert_eq!(labels.data(), &[5; 9]);
+    }
+
+    #[test]
+    fn voronoi_euclidean_picks_nearest_seed() {
+        let seeds = [
+            (Coord2D::new(17, 2), 0),
+            (Coord2D::new(3, 28), 1),
+            (Coord2D::new(8, 12), 2),
+        ];
+        let labels = Array2D::voronoi(40, 40, &seeds, DistanceMetric::Euclidean);
+        assert_eq!(labels.at((38, 30)), &2);
+    }
+
+    #[test]
+    fn voronoi_metrics_on_fixture() {
+        let seeds = [(Coord2D::new(0, 1), 0), (Coord2D::new(3, 0), 1)];
+        let euclidean = Array2D::voronoi(4, 2, &seeds, DistanceMetric::Euclidean);
+        assert_eq!(euclidean.data(), &[0, 0, 1, 1, 0, 0, 1, 1]);
+        let manhattan = Array2D::voronoi(4, 2, &seeds, DistanceMetric::Manhattan);
+        assert_eq!(manhattan.data(), &[0, 0, 1, 1, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn voronoi_breaks_ties_by_smaller_id() {
+        let seeds = [(Coord2D::new(0, 0), 1), (Coord2D::new(4, 4), 0)];
+        for &metric in &[DistanceMetric::Euclidean, DistanceMetric::Manhattan] {
+            let labels = Array2D::voronoi(5, 5, &seeds, metric);
+            for (coord, &label) in labels.iter() {
+                let expected = if coord.x + coord.y < 4 { 1 } else { 0 };
+                assert_eq!(label, expected, "{:?} at {:?}", metric, coord);
+            }
+        }
+    }
+
+    #[test]
+    fn voronoi_metrics_agree_on_a_single_row() {
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut random = |bound: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % bound as u64) as usize
+        };
+
+        for _ in 0..50 {
+            let seeds: Vec<(Coord2D, u32)> = (0..1 + random(8))
+                .map(|_| (Coord2D::new(random(60), 0), random(4) as u32))
+                .collect();
+            let euclidean = Array2D::voronoi(60, 1, &seeds, DistanceMetric::Euclidean);
+            let manhattan = Array2D::voronoi(60, 1, &seeds, DistanceMetric::Manhattan);
+            assert!(euclidean == manhattan);
+        }
+    }
+
+    #[test]
+    fn voronoi_ignores_seeds_outside_and_empty_grids() {
+        let seeds = [(Coord2D::new(9, 0), 0), (Coord2D::new(1, 1), 5)];
+        let labels = Array2D::voronoi(3, 3, &seeds, DistanceMetric::Euclidean);
+        assert_eq!(labels.data(), &[5; 9]);
+
+        let empty = Array2D::voronoi(0, 4, &[], DistanceMetric::Manhattan);
+        assert_eq!((empty.width(), empty.height()), (0, 4));
+    }
+}
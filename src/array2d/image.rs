@@ -0,0 +1,232 @@
+use crate::Array2D;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum FitMode {
+    Contain,
+    Cover,
+}
+
+impl Array2D<f32> {
+    pub fn centroid(&self) -> Option<(f64, f64)> {
+        let mut total = 0.0;
+        let mut sum_x = 0.0;
+        let mut sum_y = 0.0;
+        for (coord, &value) in self.iter() {
+            let weight = f64::from(value);
+            total += weight;
+            sum_x += weight * coord.x as f64;
+            sum_y += weight * coord.y as f64;
+        }
+
+        if total == 0.0 {
+            None
+        } else {
+            Some((sum_x / total, sum_y / total))
+        }
+    }
+
+    /// Computes the partial derivatives in x and y using central differences, falling
+    /// back to forward and backward differences at the borders.
+    pub fn gradient(&self) -> (Array2D<f32>, Array2D<f32>) {
+        let derivative = |before: usize, after: usize, a: f32, b: f32| {
+            if after > before {
+                (b - a) / (after - before) as f32
+            } else {
+                0.0
+            }
+        };
+
+        let mut dx = Array2D::new_with(self.width, self.height, 0.0);
+        let mut dy = Array2D::new_with(self.width, self.height, 0.0);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let (left, right) = (x.saturating_sub(1), (x + 1).min(self.width - 1));
+                let (up, down) = (y.saturating_sub(1), (y + 1).min(self.height - 1));
+                dx.set(
+                    (x, y),
+                    derivative(left, right, *self.at((left, y)), *self.at((right, y))),
+                );
+                dy.set(
+                    (x, y),
+                    derivative(up, down, *self.at((x, up)), *self.at((x, down))),
+                );
+            }
+        }
+        (dx, dy)
+    }
+
+    /// Computes unit surface normals of the heightfield, with `scale` exaggerating the
+    /// vertical axis. Normals point towards +Z.
+    pub fn normals(&self, scale: f32) -> Array2D<[f32; 3]> {
+        let (dx, dy) = self.gradient();
+        let data = dx
+            .data
+            .iter()
+            .zip(dy.data.iter())
+            .map(|(&dx, &dy)| {
+                let (nx, ny) = (-dx * scale, -dy * scale);
+                let length = (nx * nx + ny * ny + 1.0).sqrt();
+                [nx / length, ny / length, 1.0 / length]
+            })
+            .collect();
+        Array2D::from_raw(self.width, self.height, data)
+    }
+
+    /// Extracts the line segments where the field crosses `level` using marching squares.
+    /// Endpoints are linearly interpolated along cell edges, saddle cells are resolved
+    /// using the average of the four corners.
+    pub fn iso_contour(&self, level: f32) -> Vec<[(f32, f32); 2]> {
+        let mut segments = Vec::new();
+        for y in 0..self.height.saturating_sub(1) {
+            for x in 0..self.width.saturating_sub(1) {
+                let tl = *self.at((x, y));
+                let tr = *self.at((x + 1, y));
+                let br = *self.at((x + 1, y + 1));
+                let bl = *self.at((x, y + 1));
+
+                let lerp = |a: f32, b: f32| {
+                    if a == b {
+                        0.5
+                    } else {
+                        (level - a) / (b - a)
+                    }
+                };
+                let (fx, fy) = (x as f32, y as f32);
+                let top = (fx + lerp(tl, tr), fy);
+                let right = (fx + 1.0, fy + lerp(tr, br));
+                let bottom = (fx + lerp(bl, br), fy + 1.0);
+                let left = (fx, fy + lerp(tl, bl));
+
+                let case = (tl >= level) as u8 * 8
+                    + (tr >= level) as u8 * 4
+                    + (br >= level) as u8 * 2
+                    + (bl >= level) as u8;
+                let center_inside = (tl + tr + br + bl) / 4.0 >= level;
+                match case {
+                    1 | 14 => segments.push([left, bottom]),
+                    2 | 13 => segments.push([bottom, right]),
+                    3 | 12 => segments.push([left, right]),
+                    4 | 11 => segments.push([top, right]),
+                    6 | 9 => segments.push([top, bottom]),
+                    7 | 8 => segments.push([left, top]),
+                    5 | 10 if center_inside == (case == 5) => {
+                        segments.push([left, top]);
+                        segments.push([bottom, right]);
+                    }
+                    5 | 10 => {
+                        segments.push([top, right]);
+                        segments.push([left, bottom]);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        segments
+    }
+
+    /// Resamples the array to the given size using bilinear interpolation, aligning the
+    /// centers of the source and target cells.
+    pub fn resample_to(&self, width: usize, height: usize) -> Array2D<f32> {
+        if width == 0 || height == 0 {
+            return Array2D::from_raw(width, height, Vec::new());
+        }
+        assert!(
+            self.width > 0 && self.height > 0,
+            "cannot resample an empty array"
+        );
+
+        let source = |target: usize, from: usize, to: usize| {
+            let position = (target as f32 + 0.5) * from as f32 / to as f32 - 0.5;
+            let position = position.max(0.0).min((from - 1) as f32);
+            let low = position.floor() as usize;
+            (low, (low + 1).min(from - 1), position - low as f32)
+        };
+
+        let mut data = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let (y0, y1, ty) = source(y, self.height, height);
+            for x in 0..width {
+                let (x0, x1, tx) = source(x, self.width, width);
+                let top = self.at((x0, y0)) + (self.at((x1, y0)) - self.at((x0, y0))) * tx;
+                let bottom = self.at((x0, y1)) + (self.at((x1, y1)) - self.at((x0, y1))) * tx;
+                data.push(top + (bottom - top) * ty);
+            }
+        }
+        Array2D::from_raw(width, height, data)
+    }
+
+    /// Resizes the array while preserving its aspect ratio. `Contain` returns the largest
+    /// size fitting into `max_width x max_height`, `Cover` scales to the smallest size
+    /// covering it and crops the center to exactly `max_width x max_height`.
+    pub fn resize_fit(&self, max_width: usize, max_height: usize, mode: FitMode) -> Array2D<f32> {
+        assert!(self.width > 0 && self.height > 0);
+        assert!(max_width > 0 && max_height > 0);
+
+        let scale_x = max_width as f64 / self.width as f64;
+        let scale_y = max_height as f64 / self.height as f64;
+        let scaled = |size: usize, scale: f64| ((size as f64 * scale).round() as usize).max(1);
+        match mode {
+            FitMode::Contain => {
+                let scale = scale_x.min(scale_y);
+                let width = scaled(self.width, scale).min(max_width);
+                let height = scaled(self.height, scale).min(max_height);
+                self.resample_to(width, height)
+            }
+            FitMode::Cover => {
+                let scale = scale_x.max(scale_y);
+                let width = scaled(self.width, scale).max(max_width);
+                let height = scaled(self.height, scale).max(max_height);
+                let resampled = self.resample_to(width, height);
+                resampled.sub(
+                    ((width - max_width) / 2, (height - max_height) / 2),
+                    max_width,
+                    max_height,
+                )
+            }
+        }
+    }
+}
+
+impl Array2D<u8> {
+    pub fn histogram_u8(&self) -> [usize; 256] {
+        let mut histogram = [0; 256];
+        for &value in self.data.iter() {
+            histogram[value as usize] += 1;
+        }
+        histogram
+    }
+
+    pub fn equalize(&self) -> Array2D<u8> {
+        let histogram = self.histogram_u8();
+        let total = self.data.len();
+        let cdf_min = histogram
+            .iter()
+            .cloned()
+            .find(|&count| count > 0)
+            .unwrap_or(0);
+        if total == cdf_min {
+            return self.clone();
+        }
+
+        let mut lut = [0u8; 256];
+        let mut cdf = 0;
+        for (value, &count) in histogram.iter().enumerate() {
+            cdf += count;
+            let scaled = (cdf.saturating_sub(cdf_min) as f64 / (total - cdf_min) as f64) * 255.0;
+            lut[value] = scaled.round() as u8;
+        }
+        self.apply_lut(&lut)
+    }
+
+    pub fn apply_lut(&self, lut: &[u8; 256]) -> Array2D<u8> {
+        let mut result = self.clone();
+        result.apply_lut_mut(lut);
+        result
+    }
+
+    pub fn apply_lut_mut(&mut self, lut: &[u8; 256]) {
+        for value in self.data.iter_mut() {
+            *value = lut[*value as usize];
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use crate::{Array2D, Coord2D};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum MorphOp {
+    Erode,
+    Dilate,
+}
+
+impl Array2D<bool> {
+    /// Creates an `n x n` mask that is `true` on the diagonal and `false` elsewhere.
+    /// Identity matrices are square by definition, non-square shapes are not supported.
+    pub fn identity(n: usize) -> Self {
+        let mut array = Self::new_with(n, n, false);
+        for i in 0..n {
+            array.set((i, i), true);
+        }
+        array
+    }
+
+    pub fn true_coords(&self) -> Vec<Coord2D> {
+        self.iter()
+            .filter(|&(_, &value)| value)
+            .map(|(coord, _)| coord)
+            .collect()
+    }
+
+    pub fn centroid(&self) -> Option<(f64, f64)> {
+        let mut count = 0usize;
+        let mut sum_x = 0.0;
+        let mut sum_y = 0.0;
+        for (coord, _) in self.iter().filter(|&(_, &value)| value) {
+            count += 1;
+            sum_x += coord.x as f64;
+            sum_y += coord.y as f64;
+        }
+
+        if count == 0 {
+            None
+        } else {
+            Some((sum_x / count as f64, sum_y / count as f64))
+        }
+    }
+
+    pub fn xor_mask(&self, mask: &Array2D<bool>) -> Array2D<bool> {
+        let mut result = self.clone();
+        result.xor_mask_in_place(mask);
+        result
+    }
+
+    pub fn xor_mask_in_place(&mut self, mask: &Array2D<bool>) {
+        self.combine_mask(mask, |a, b| a ^ b);
+    }
+
+    pub fn and_mask(&self, mask: &Array2D<bool>) -> Array2D<bool> {
+        let mut result = self.clone();
+        result.and_mask_in_place(mask);
+        result
+    }
+
+    pub fn and_mask_in_place(&mut self, mask: &Array2D<bool>) {
+        self.combine_mask(mask, |a, b| a & b);
+    }
+
+    pub fn or_mask(&self, mask: &Array2D<bool>) -> Array2D<bool> {
+        let mut result = self.clone();
+        result.or_mask_in_place(mask);
+        result
+    }
+
+    pub fn or_mask_in_place(&mut self, mask: &Array2D<bool>) {
+        self.combine_mask(mask, |a, b| a | b);
+    }
+
+    fn combine_mask<F: Fn(bool, bool) -> bool>(&mut self, mask: &Array2D<bool>, f: F) {
+        assert!(self.width == mask.width);
+        assert!(self.height == mask.height);
+
+        for (value, &other) in self.data.iter_mut().zip(mask.data.iter()) {
+            *value = f(*value, other);
+        }
+    }
+
+    /// Packs the mask into one bit per cell, most significant bit first. Every row
+    /// starts on a new byte, unused padding bits at the end of a row are zero.
+    pub fn pack_bits(&self) -> (Vec<u8>, usize, usize) {
+        let row_bytes = self.width.div_ceil(8);
+        let mut data = vec![0u8; row_bytes * self.height];
+        for (coord, _) in self.iter().filter(|&(_, &value)| value) {
+            data[row_bytes * coord.y + coord.x / 8] |= 0x80 >> (coord.x % 8);
+        }
+        (data, self.width, self.height)
+    }
+
+    pub fn unpack_bits(data: Vec<u8>, width: usize, height: usize) -> Array2D<bool> {
+        let row_bytes = width.div_ceil(8);
+        assert!(data.len() == row_bytes * height);
+
+        let mut result = Self::new_with(width, height, false);
+        for (coord, value) in result.iter_mut() {
+            *value = data[row_bytes * coord.y + coord.x / 8] & (0x80 >> (coord.x % 8)) != 0;
+        }
+        result
+    }
+
+    /// Applies a morphological operation using `element` as structuring element,
+    /// anchored at its center cell `(width / 2, height / 2)`.
+    ///
+    /// Cells outside of the array are treated as `false` when dilating and as `true`
+    /// when eroding, so that the array border neither grows nor shrinks regions.
+    pub fn morph(&self, element: &Array2D<bool>, op: MorphOp) -> Array2D<bool> {
+        let anchor_x = (element.width / 2) as isize;
+        let anchor_y = (element.height / 2) as isize;
+        let outside = op == MorphOp::Erode;
+
+        let mut result = Self::new_with(self.width, self.height, false);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let mut hits = element.iter().filter(|&(_, &set)| set).map(|(e, _)| {
+                    let sx = x as isize + e.x as isize - anchor_x;
+                    let sy = y as isize + e.y as isize - anchor_y;
+                    if sx < 0 || sy < 0 || sx as usize >= self.width || sy as usize >= self.height {
+                        outside
+                    } else {
+                        *self.at((sx as usize, sy as usize))
+                    }
+                });
+                let value = match op {
+                    MorphOp::Erode => hits.all(|hit| hit),
+                    MorphOp::Dilate => hits.any(|hit| hit),
+                };
+                result.set((x, y), value);
+            }
+        }
+        result
+    }
+
+    /// Thins all regions down to a one cell wide skeleton using the Zhang-Suen algorithm.
+    pub fn skeletonize(&self) -> Array2D<bool> {
+        self.thin(false)
+    }
+
+    /// Thins all regions like `skeletonize`, but never changes their topology: cells are
+    /// removed one at a time and only while their removal neither splits nor erases a
+    /// region nor opens a hole, and cells ending a line are kept. Small blocks and thick
+    /// diagonal lines therefore keep a skeleton instead of vanishing.
+    pub fn skeleton(&self) -> Array2D<bool> {
+        self.thin(true)
+    }
+
+    fn thin(&self, guarded: bool) -> Array2D<bool> {
+        let mut result = self.clone();
+        let mut changed = true;
+        while changed {
+            changed = result.zhang_suen_pass(true, guarded);
+            changed |= result.zhang_suen_pass(false, guarded);
+        }
+        result
+    }
+
+    fn zhang_suen_pass(&mut self, first: bool, guarded: bool) -> bool {
+        let mut removed = Vec::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if !*self.at((x, y)) {
+                    continue;
+                }
+
+                // P2..P9, clockwise starting north of (x, y)
+                let p = self.ring(x, y);
+                let (a, b) = if first {
+                    (p[0] && p[2] && p[4], p[2] && p[4] && p[6])
+                } else {
+                    (p[0] && p[2] && p[6], p[0] && p[4] && p[6])
+                };
+
+                if Self::is_simple(&p) && !a && !b {
+                    removed.push((x, y));
+                }
+            }
+        }
+
+        // A guarded pass re-checks each cell against the cells already removed, and keeps
+        // cells left with only two adjacent neighbors, as they end a line.
+        let mut changed = false;
+        for &(x, y) in removed.iter() {
+            let ring = self.ring(x, y);
+            if !guarded || (Self::is_simple(&ring) && ring.iter().filter(|&&set| set).count() > 2) {
+                self.set((x, y), false);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Whether a cell with the given ring of neighbors has between 2 and 6 set neighbors
+    /// forming a single run around it.
+    fn is_simple(ring: &[bool; 8]) -> bool {
+        let neighbors = ring.iter().filter(|&&set| set).count();
+        let transitions = (0..8).filter(|&i| !ring[i] && ring[(i + 1) % 8]).count();
+        (2..=6).contains(&neighbors) && transitions == 1
+    }
+
+    fn ring(&self, x: usize, y: usize) -> [bool; 8] {
+        let offsets = [
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+        ];
+        let mut ring = [false; 8];
+        for (i, &(dx, dy)) in offsets.iter().enumerate() {
+            let nx = x as isize + dx;
+            let ny = y as isize + dy;
+            if nx >= 0 && ny >= 0 && (nx as usize) < self.width && (ny as usize) < self.height {
+                ring[i] = *self.at((nx as usize, ny as usize));
+            }
+        }
+        ring
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Connectivity;
+
+    fn mask(width: usize, height: usize, cells: &[(usize, usize)]) -> Array2D<bool> {
+        let mut mask = Array2D::new_with(width, height, false);
+        for &cell in cells {
+            mask.set(cell, true);
+        }
+        mask
+    }
+
+    fn holes(mask: &Array2D<bool>) -> usize {
+        let mut background = Array2D::new_with(mask.width() + 2, mask.height() + 2, true);
+        for (c, &set) in mask.iter() {
+            background.set((c.x + 1, c.y + 1), !set);
+        }
+        background.count_regions(Connectivity::Four) - 1
+    }
+
+    #[test]
+    fn skeleton_keeps_small_blocks() {
+        let block = mask(4, 4, &[(1, 1), (2, 1), (1, 2), (2, 2)]);
+        assert!(block.skeletonize().iter().all(|(_, &set)| !set));
+
+        let skeleton = block.skeleton();
+        assert_eq!((skeleton.width(), skeleton.height()), (4, 4));
+        assert!(skeleton.iter().any(|(_, &set)| set));
+        assert_eq!(skeleton.count_regions(Connectivity::Eight), 1);
+    }
+
+    #[test]
+    fn skeleton_keeps_thick_diagonals_connected() {
+        let cells: Vec<_> = (0..4)
+            .flat_map(|i| vec![(i + 1, i + 1), (i + 2, i + 1)])
+            .collect();
+        let line = mask(8, 8, &cells);
+
+        let skeleton = line.skeleton();
+        assert_eq!(skeleton.count_regions(Connectivity::Eight), 1);
+        assert!(skeleton.iter().all(|(c, &set)| !set || *line.at(c)));
+        assert!(skeleton.iter().filter(|&(_, &set)| set).count() >= 4);
+    }
+
+    #[test]
+    fn skeleton_keeps_holes() {
+        let mut ring = Array2D::new_with(9, 9, false);
+        for y in 1..8 {
+            for x in 1..8 {
+                ring.set((x, y), !(3..6).contains(&x) || !(3..6).contains(&y));
+            }
+        }
+        assert_eq!(holes(&ring), 1);
+
+        let skeleton = ring.skeleton();
+        assert_eq!(skeleton.count_regions(Connectivity::Eight), 1);
+        assert_eq!(holes(&skeleton), 1);
+    }
+}
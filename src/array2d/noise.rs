@@ -0,0 +1,62 @@
+use crate::Array2D;
+
+fn lattice_value(x: i64, y: i64, seed: u64) -> f32 {
+    let mut hash = seed
+        ^ (x as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+        ^ (y as u64).wrapping_mul(0xc2b2_ae3d_27d4_eb4f);
+    hash ^= hash >> 30;
+    hash = hash.wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash ^= hash >> 27;
+    hash = hash.wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^= hash >> 31;
+    (hash >> 40) as f32 / (1u64 << 24) as f32
+}
+
+fn smooth_value(x: f32, y: f32, seed: u64) -> f32 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let smoothstep = |t: f32| t * t * (3.0 - 2.0 * t);
+    let (tx, ty) = (smoothstep(x - x0), smoothstep(y - y0));
+    let (ix, iy) = (x0 as i64, y0 as i64);
+
+    let top = lattice_value(ix, iy, seed)
+        + (lattice_value(ix + 1, iy, seed) - lattice_value(ix, iy, seed)) * tx;
+    let bottom = lattice_value(ix, iy + 1, seed)
+        + (lattice_value(ix + 1, iy + 1, seed) - lattice_value(ix, iy + 1, seed)) * tx;
+    top + (bottom - top) * ty
+}
+
+impl Array2D<f32> {
+    /// Generates smooth value noise in `[0, 1)`, with random values placed on a lattice
+    /// every `scale` cells and interpolated in between. The same seed always produces
+    /// the same field.
+    pub fn value_noise(width: usize, height: usize, scale: f32, seed: u64) -> Self {
+        Self::fbm(width, height, scale, 1, seed)
+    }
+
+    /// Sums `octaves` layers of value noise, each with half the feature scale and half
+    /// the amplitude of the previous one, normalized to `[0, 1)`.
+    pub fn fbm(width: usize, height: usize, scale: f32, octaves: u32, seed: u64) -> Self {
+        assert!(scale > 0.0, "noise scale must be positive");
+        assert!(octaves > 0, "fbm needs at least one octave");
+
+        let mut data = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let mut value = 0.0;
+                let mut amplitude = 1.0;
+                let mut total = 0.0;
+                let mut frequency = 1.0 / scale;
+                for octave in 0..octaves {
+                    let octave_seed = seed.wrapping_add(u64::from(octave));
+                    value += amplitude
+                        * smooth_value(x as f32 * frequency, y as f32 * frequency, octave_seed);
+                    total += amplitude;
+                    amplitude *= 0.5;
+                    frequency *= 2.0;
+                }
+                data.push(value / total);
+            }
+        }
+        Array2D::from_raw(width, height, data)
+    }
+}
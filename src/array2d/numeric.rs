@@ -0,0 +1,445 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::iter::{Product, Sum};
+use std::ops::{Add, Div, Mul, Sub};
+
+use num_traits::{Float, One, Zero};
+
+use crate::Array2D;
+
+impl<T: Clone> Array2D<T> {
+    pub fn sum_all<S: Sum<T>>(&self) -> S {
+        self.data.iter().cloned().sum()
+    }
+
+    pub fn product_all<P: Product<T>>(&self) -> P {
+        self.data.iter().cloned().product()
+    }
+
+    pub fn trace<S: Sum<T>>(&self) -> S {
+        assert!(self.width == self.height);
+        self.diag().into_iter().cloned().sum()
+    }
+
+    pub fn matrix_mul<U, V>(&self, rhs: &Array2D<U>) -> Array2D<V>
+    where
+        T: Mul<U, Output = V>,
+        U: Clone,
+        V: Clone + Zero,
+    {
+        assert!(self.width == rhs.height);
+
+        let mut data: Vec<V> = Vec::with_capacity(rhs.width * self.height);
+        for y in 0..self.height {
+            for x in 0..rhs.width {
+                let mut sum = V::zero();
+                for k in 0..self.width {
+                    sum = sum + self.at((k, y)).clone() * rhs.at((x, k)).clone();
+                }
+                data.push(sum);
+            }
+        }
+        Array2D {
+            width: rhs.width,
+            height: self.height,
+            data,
+        }
+    }
+
+    pub fn mul_scalar<S: Copy>(&self, scalar: S) -> Self
+    where
+        T: Mul<S, Output = T>,
+    {
+        let data = self
+            .data
+            .iter()
+            .map(|value| value.clone() * scalar)
+            .collect();
+        Array2D {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+
+    pub fn mul_scalar_in_place<S: Copy>(&mut self, scalar: S)
+    where
+        T: Mul<S, Output = T>,
+    {
+        for value in self.data.iter_mut() {
+            *value = value.clone() * scalar;
+        }
+    }
+
+    pub fn add_scalar<S: Copy>(&self, scalar: S) -> Self
+    where
+        T: Add<S, Output = T>,
+    {
+        let data = self
+            .data
+            .iter()
+            .map(|value| value.clone() + scalar)
+            .collect();
+        Array2D {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+
+    pub fn add_scalar_in_place<S: Copy>(&mut self, scalar: S)
+    where
+        T: Add<S, Output = T>,
+    {
+        for value in self.data.iter_mut() {
+            *value = value.clone() + scalar;
+        }
+    }
+
+    pub fn sub_scalar<S: Copy>(&self, scalar: S) -> Self
+    where
+        T: Sub<S, Output = T>,
+    {
+        let data = self
+            .data
+            .iter()
+            .map(|value| value.clone() - scalar)
+            .collect();
+        Array2D {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+
+    pub fn sub_scalar_in_place<S: Copy>(&mut self, scalar: S)
+    where
+        T: Sub<S, Output = T>,
+    {
+        for value in self.data.iter_mut() {
+            *value = value.clone() - scalar;
+        }
+    }
+
+    pub fn div_scalar<S: Copy>(&self, scalar: S) -> Self
+    where
+        T: Div<S, Output = T>,
+    {
+        let data = self
+            .data
+            .iter()
+            .map(|value| value.clone() / scalar)
+            .collect();
+        Array2D {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+
+    pub fn div_scalar_in_place<S: Copy>(&mut self, scalar: S)
+    where
+        T: Div<S, Output = T>,
+    {
+        for value in self.data.iter_mut() {
+            *value = value.clone() / scalar;
+        }
+    }
+}
+
+impl<T: Clone + Ord> Array2D<T> {
+    pub fn max_elementwise(&self, other: &Self) -> Self {
+        self.zip_elementwise(other, |a, b| a.max(b))
+    }
+
+    pub fn min_elementwise(&self, other: &Self) -> Self {
+        self.zip_elementwise(other, |a, b| a.min(b))
+    }
+
+    pub fn rolling_max_row(&self, window: usize) -> Self {
+        self.rolling_row(window, |kept, new| kept > new)
+    }
+
+    pub fn rolling_min_row(&self, window: usize) -> Self {
+        self.rolling_row(window, |kept, new| kept < new)
+    }
+
+    fn rolling_row<F: Fn(&T, &T) -> bool>(&self, window: usize, dominates: F) -> Self {
+        let half = window / 2;
+        let mut data = Vec::with_capacity(self.data.len());
+        let mut deque: VecDeque<usize> = VecDeque::new();
+
+        for row in self.data.chunks(self.width.max(1)) {
+            deque.clear();
+            let mut next = 0;
+            for x in 0..row.len() {
+                let right = (x + half).min(row.len() - 1);
+                while next <= right {
+                    while deque
+                        .back()
+                        .is_some_and(|&i| !dominates(&row[i], &row[next]))
+                    {
+                        deque.pop_back();
+                    }
+                    deque.push_back(next);
+                    next += 1;
+                }
+                while deque.front().is_some_and(|&i| i + half < x) {
+                    deque.pop_front();
+                }
+                data.push(row[deque[0]].clone());
+            }
+        }
+
+        Array2D {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+
+    fn zip_elementwise<F: Fn(T, T) -> T>(&self, other: &Self, f: F) -> Self {
+        assert!(self.width == other.width);
+        assert!(self.height == other.height);
+
+        let data = self
+            .data
+            .iter()
+            .zip(other.data.iter())
+            .map(|(a, b)| f(a.clone(), b.clone()))
+            .collect();
+        Array2D {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+}
+
+impl<T: Clone + Sub<Output = T> + PartialOrd> Array2D<T> {
+    /// Returns for every cell whether `|self - other| <= tolerance`.
+    pub fn compare_with_tolerance(&self, other: &Self, tolerance: T) -> Array2D<bool> {
+        let data = self
+            .differences(other)
+            .map(|difference| difference <= tolerance)
+            .collect();
+        Array2D {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+
+    pub fn all_within_tolerance(&self, other: &Self, tolerance: T) -> bool {
+        self.differences(other)
+            .all(|difference| difference <= tolerance)
+    }
+
+    /// Returns the largest absolute difference between corresponding cells, or `None` if
+    /// the arrays are empty.
+    pub fn max_difference(&self, other: &Self) -> Option<T> {
+        self.differences(other)
+            .fold(None, |max, difference| match max {
+                Some(max) if max >= difference => Some(max),
+                _ => Some(difference),
+            })
+    }
+
+    fn differences<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = T> + 'a {
+        assert!(self.width == other.width);
+        assert!(self.height == other.height);
+
+        self.data.iter().zip(other.data.iter()).map(|(a, b)| {
+            if a >= b {
+                a.clone() - b.clone()
+            } else {
+                b.clone() - a.clone()
+            }
+        })
+    }
+}
+
+impl<T: Clone + Zero> Array2D<T> {
+    pub fn zeros(width: usize, height: usize) -> Self {
+        Self::new_with(width, height, T::zero())
+    }
+}
+
+impl<T: Clone + One> Array2D<T> {
+    pub fn ones(width: usize, height: usize) -> Self {
+        Self::new_with(width, height, T::one())
+    }
+}
+
+impl<T: Clone + Zero + One> Array2D<T> {
+    /// Creates an `n x n` matrix with ones on the diagonal and zeros elsewhere.
+    /// Identity matrices are square by definition, non-square shapes are not supported.
+    pub fn identity_numeric(n: usize) -> Self {
+        let mut array = Self::new_with(n, n, T::zero());
+        for i in 0..n {
+            array.set((i, i), T::one());
+        }
+        array
+    }
+}
+
+impl<T: Clone + Mul<Output = T>> Array2D<T> {
+    pub fn from_row_col_slices(rows: &[T], cols: &[T]) -> Self {
+        Self::outer_product(rows, cols, |row, col| row.clone() * col.clone())
+    }
+}
+
+impl<T: Clone + Sub<Output = T> + Mul<Output = T>> Array2D<T> {
+    pub fn det2x2(&self) -> T {
+        assert!(self.width == 2 && self.height == 2);
+        let m = |x: usize, y: usize| self.at((x, y)).clone();
+        m(0, 0) * m(1, 1) - m(1, 0) * m(0, 1)
+    }
+
+    pub fn det3x3(&self) -> T
+    where
+        T: Add<Output = T>,
+    {
+        assert!(self.width == 3 && self.height == 3);
+        let m = |x: usize, y: usize| self.at((x, y)).clone();
+        m(0, 0) * (m(1, 1) * m(2, 2) - m(2, 1) * m(1, 2))
+            - m(1, 0) * (m(0, 1) * m(2, 2) - m(2, 1) * m(0, 2))
+            + m(2, 0) * (m(0, 1) * m(1, 2) - m(1, 1) * m(0, 2))
+    }
+}
+
+impl<T: Clone + Eq + Hash> Array2D<T> {
+    pub fn histogram(&self) -> HashMap<T, usize> {
+        let mut histogram = HashMap::new();
+        for value in self.data.iter() {
+            *histogram.entry(value.clone()).or_insert(0) += 1;
+        }
+        histogram
+    }
+
+    pub fn entropy(&self) -> f64 {
+        let total = self.data.len() as f64;
+        self.histogram()
+            .values()
+            .map(|&count| {
+                let p = count as f64 / total;
+                p * (1.0 / p).log2()
+            })
+            .sum()
+    }
+}
+
+impl<T: Clone + Into<f64>> Array2D<T> {
+    /// Returns the arithmetic mean of all elements, or NaN if the array is empty.
+    pub fn mean_all(&self) -> f64 {
+        let sum: f64 = self.data.iter().cloned().map(Into::into).sum();
+        sum / self.data.len() as f64
+    }
+
+    /// Returns the population variance of all elements, or NaN if the array is empty.
+    pub fn variance_all(&self) -> f64 {
+        let mean = self.mean_all();
+        let sum: f64 = self
+            .data
+            .iter()
+            .cloned()
+            .map(|value| {
+                let diff = value.into() - mean;
+                diff * diff
+            })
+            .sum();
+        sum / self.data.len() as f64
+    }
+
+    /// Returns the population standard deviation, or NaN if the array is empty.
+    pub fn std_dev_all(&self) -> f64 {
+        self.variance_all().sqrt()
+    }
+
+    /// Convolves the array with a kernel given as a function of the offset `(dx, dy)`,
+    /// for offsets within `radius`. Samples outside the array are clamped to the border.
+    /// The kernel is evaluated on the fly for every cell and offset, so no kernel array
+    /// is allocated.
+    pub fn convolve_with_fn<F: Fn(isize, isize) -> f64>(
+        &self,
+        radius: usize,
+        kernel_fn: F,
+    ) -> Array2D<f64> {
+        let radius = radius as isize;
+        let clamp = |value: isize, size: usize| value.clamp(0, size as isize - 1) as usize;
+        let mut data: Vec<f64> = Vec::with_capacity(self.data.len());
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let mut value = 0.0;
+                for dy in -radius..=radius {
+                    let sy = clamp(y as isize + dy, self.height);
+                    for dx in -radius..=radius {
+                        let sx = clamp(x as isize + dx, self.width);
+                        value += kernel_fn(dx, dy) * self.at((sx, sy)).clone().into();
+                    }
+                }
+                data.push(value);
+            }
+        }
+        Array2D::from_raw(self.width, self.height, data)
+    }
+}
+
+impl<T: Clone + Float> Array2D<T> {
+    pub fn approx_eq(&self, other: &Self, epsilon: T) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self
+                .data
+                .iter()
+                .zip(other.data.iter())
+                .all(|(&a, &b)| (a - b).abs() <= epsilon)
+    }
+}
+
+impl<T: Clone + Float + Debug> Array2D<T> {
+    pub fn assert_approx_eq(&self, other: &Self, epsilon: T) {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "dimension mismatch: actual {}x{}, expected {}x{}",
+            self.width,
+            self.height,
+            other.width,
+            other.height
+        );
+
+        for (coord, &actual) in self.iter() {
+            let expected = *other.at(coord);
+            let difference = (actual - expected).abs();
+            assert!(
+                difference <= epsilon,
+                "arrays differ at ({}, {}): expected {:?}, actual {:?}, difference {:?} > {:?}",
+                coord.x,
+                coord.y,
+                expected,
+                actual,
+                difference,
+                epsilon
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convolve_with_fn_clamps_borders() {
+        let array = Array2D::from_data(3, 1, vec![1.0f32, 2.0, 4.0]).unwrap();
+        let identity = array.convolve_with_fn(1, |dx, dy| (dx == 0 && dy == 0) as u8 as f64);
+        assert_eq!(identity.data(), &[1.0, 2.0, 4.0]);
+
+        let box_blur = array.convolve_with_fn(1, |_, _| 1.0 / 9.0);
+        let expected = [4.0 / 3.0, 7.0 / 3.0, 10.0 / 3.0];
+        for (actual, expected) in box_blur.data().iter().zip(expected.iter()) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+    }
+}
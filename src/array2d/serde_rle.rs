@@ -0,0 +1,138 @@
+//! Run-length encoded serde representation of `Array2D`, to be used on fields via
+//! `#[serde(with = "array2d::serde_rle")]`.
+//!
+//! A few bytes of encoded runs can describe an arbitrarily large array, so decoding
+//! untrusted input could otherwise exhaust memory. Deserialization therefore rejects
+//! arrays with more than `MAX_CELLS` cells before allocating anything.
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Array2D;
+
+/// Largest number of cells `deserialize` accepts.
+pub const MAX_CELLS: usize = 1 << 26;
+
+#[derive(Serialize)]
+struct RleRef<'a, T: 'a> {
+    width: usize,
+    height: usize,
+    runs: Vec<(usize, &'a T)>,
+}
+
+#[derive(Deserialize)]
+struct Rle<T> {
+    width: usize,
+    height: usize,
+    runs: Vec<(usize, T)>,
+}
+
+pub fn serialize<S, T>(array: &Array2D<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Clone + PartialEq + Serialize,
+{
+    let mut runs: Vec<(usize, &T)> = Vec::new();
+    for value in array.data.iter() {
+        match runs.last_mut() {
+            Some(&mut (ref mut count, last)) if last == value => *count += 1,
+            _ => runs.push((1, value)),
+        }
+    }
+
+    RleRef {
+        width: array.width,
+        height: array.height,
+        runs,
+    }
+    .serialize(serializer)
+}
+
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Array2D<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Clone + Deserialize<'de>,
+{
+    let rle = Rle::<T>::deserialize(deserializer)?;
+    let expected = rle
+        .width
+        .checked_mul(rle.height)
+        .filter(|&cells| cells <= MAX_CELLS)
+        .ok_or_else(|| D::Error::custom(format!("array exceeds {} cells", MAX_CELLS)))?;
+    let actual = rle
+        .runs
+        .iter()
+        .try_fold(0usize, |total, &(count, _)| total.checked_add(count))
+        .filter(|&total| total == expected)
+        .ok_or_else(|| D::Error::custom(format!("run lengths do not add up to {}", expected)))?;
+
+    let mut data: Vec<T> = Vec::with_capacity(actual);
+    for (count, value) in rle.runs {
+        let len = data.len() + count;
+        data.resize(len, value);
+    }
+    Ok(Array2D::from_raw(rle.width, rle.height, data))
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    extern crate serde_json;
+
+    use serde::{Deserialize, Serialize};
+
+    use super::MAX_CELLS;
+    use crate::Array2D;
+
+    #[derive(Serialize, Deserialize)]
+    struct Wrapper {
+        #[serde(with = "super")]
+        array: Array2D<u8>,
+    }
+
+    fn decode(json: &str) -> Result<Array2D<u8>, String> {
+        serde_json::from_str::<Wrapper>(json)
+            .map(|wrapper| wrapper.array)
+            .map_err(|error| error.to_string())
+    }
+
+    #[test]
+    fn round_trip() {
+        let array = Array2D::from_data(3, 2, vec![1, 1, 2, 2, 2, 3]).unwrap();
+        let json = serde_json::to_string(&Wrapper {
+            array: array.clone(),
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            r#"{"array":{"width":3,"height":2,"runs":[[2,1],[3,2],[1,3]]}}"#
+        );
+        assert!(decode(&json).unwrap() == array);
+    }
+
+    #[test]
+    fn rejects_mismatched_run_lengths() {
+        let short = r#"{"array":{"width":2,"height":2,"runs":[[3,0]]}}"#;
+        assert!(decode(short).unwrap_err().contains("run lengths"));
+        let long = r#"{"array":{"width":2,"height":2,"runs":[[3,0],[2,1]]}}"#;
+        assert!(decode(long).unwrap_err().contains("run lengths"));
+    }
+
+    #[test]
+    fn rejects_oversized_arrays_before_allocating() {
+        let overflow = format!(
+            r#"{{"array":{{"width":{},"height":2,"runs":[[1,0]]}}}}"#,
+            usize::MAX
+        );
+        assert!(decode(&overflow).unwrap_err().contains("cells"));
+
+        let huge = r#"{"array":{"width":1099511627776,"height":1,"runs":[[1099511627776,0]]}}"#;
+        assert!(decode(huge).unwrap_err().contains("cells"));
+
+        let just_over = format!(
+            r#"{{"array":{{"width":{},"height":1,"runs":[[{},0]]}}}}"#,
+            MAX_CELLS + 1,
+            MAX_CELLS + 1
+        );
+        assert!(decode(&just_over).unwrap_err().contains("cells"));
+    }
+}
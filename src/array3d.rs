@@ -1,6 +1,14 @@
-use crate::Array2D;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Index, IndexMut};
 
-#[derive(Clone)]
+use num_traits::{Float, One, Zero};
+
+mod field;
+
+use crate::{Array2D, LenMismatch};
+
+#[derive(Clone, PartialEq, Eq)]
 pub struct Array3D<T: Clone> {
     width: usize,
     height: usize,
@@ -19,6 +27,37 @@ impl Coord3D {
     pub fn new(x: usize, y: usize, z: usize) -> Self {
         Coord3D { x, y, z }
     }
+
+    pub fn to_f64(self) -> (f64, f64, f64) {
+        (self.x as f64, self.y as f64, self.z as f64)
+    }
+
+    pub fn from_f64_round(x: f64, y: f64, z: f64) -> Option<Self> {
+        Self::from_f64_with(x, y, z, f64::round)
+    }
+
+    pub fn from_f64_floor(x: f64, y: f64, z: f64) -> Option<Self> {
+        Self::from_f64_with(x, y, z, f64::floor)
+    }
+
+    pub fn from_f64_ceil(x: f64, y: f64, z: f64) -> Option<Self> {
+        Self::from_f64_with(x, y, z, f64::ceil)
+    }
+
+    fn from_f64_with<F: Fn(f64) -> f64>(x: f64, y: f64, z: f64, op: F) -> Option<Self> {
+        let (x, y, z) = (op(x), op(y), op(z));
+        if x >= 0.0
+            && y >= 0.0
+            && z >= 0.0
+            && x < usize::MAX as f64
+            && y < usize::MAX as f64
+            && z < usize::MAX as f64
+        {
+            Some(Coord3D::new(x as usize, y as usize, z as usize))
+        } else {
+            None
+        }
+    }
 }
 
 impl From<(usize, usize, usize)> for Coord3D {
@@ -107,6 +146,32 @@ impl<T: Clone> Array3D<T> {
         }
     }
 
+    /// Wraps a vector ordered by x, then y, then z without copying, failing if its length
+    /// is not `width * height * depth`.
+    pub fn from_data(
+        width: usize,
+        height: usize,
+        depth: usize,
+        data: Vec<T>,
+    ) -> Result<Self, LenMismatch> {
+        if data.len() != width * height * depth {
+            return Err(LenMismatch {
+                expected: width * height * depth,
+                actual: data.len(),
+            });
+        }
+        Ok(Array3D {
+            width,
+            height,
+            depth,
+            data,
+        })
+    }
+
+    pub fn into_data(self) -> Vec<T> {
+        self.data
+    }
+
     pub fn width(&self) -> usize {
         self.width
     }
@@ -127,20 +192,43 @@ impl<T: Clone> Array3D<T> {
         coord.x + self.width * coord.y + self.width * self.height * coord.z
     }
 
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             this: self,
             coord: Coord3D::new(0, 0, 0),
         }
     }
 
-    pub fn iter_mut(&mut self) -> IterMut<T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         IterMut {
             this: self,
             coord: Coord3D::new(0, 0, 0),
         }
     }
 
+    pub fn iter_region<C: Into<Coord3D>>(
+        &self,
+        coord: C,
+        width: usize,
+        height: usize,
+        depth: usize,
+    ) -> impl Iterator<Item = (Coord3D, &T)> {
+        let origin = coord.into();
+        assert!(origin.x + width <= self.width);
+        assert!(origin.y + height <= self.height);
+        assert!(origin.z + depth <= self.depth);
+
+        (origin.z..origin.z + depth).flat_map(move |z| {
+            (origin.y..origin.y + height).flat_map(move |y| {
+                let begin = self.coord_index(Coord3D::new(origin.x, y, z));
+                self.data[begin..begin + width]
+                    .iter()
+                    .enumerate()
+                    .map(move |(i, value)| (Coord3D::new(origin.x + i, y, z), value))
+            })
+        })
+    }
+
     pub fn data(&self) -> &[T] {
         &self.data
     }
@@ -149,17 +237,47 @@ impl<T: Clone> Array3D<T> {
         &mut self.data
     }
 
-    pub fn at<C: Into<Coord3D>>(&self, coord: C) -> &T {
+    pub fn get<C: Into<Coord3D>>(&self, coord: C) -> Option<&T> {
         let coord = coord.into();
-        assert!(self.coord_is_valid(coord));
-        &self.data[self.coord_index(coord)]
+        if self.coord_is_valid(coord) {
+            Some(&self.data[self.coord_index(coord)])
+        } else {
+            None
+        }
     }
 
-    pub fn at_mut<C: Into<Coord3D>>(&mut self, coord: C) -> &mut T {
+    pub fn get_mut<C: Into<Coord3D>>(&mut self, coord: C) -> Option<&mut T> {
         let coord = coord.into();
-        assert!(self.coord_is_valid(coord));
-        let index = self.coord_index(coord);
-        &mut self.data[index]
+        if self.coord_is_valid(coord) {
+            let index = self.coord_index(coord);
+            Some(&mut self.data[index])
+        } else {
+            None
+        }
+    }
+
+    pub fn at<C: Into<Coord3D>>(&self, coord: C) -> &T {
+        self.get(coord).expect("coordinate out of bounds")
+    }
+
+    pub fn at_mut<C: Into<Coord3D>>(&mut self, coord: C) -> &mut T {
+        self.get_mut(coord).expect("coordinate out of bounds")
+    }
+
+    pub fn at_xyz(&self, x: usize, y: usize, z: usize) -> &T {
+        self.at(Coord3D::new(x, y, z))
+    }
+
+    pub fn at_xyz_mut(&mut self, x: usize, y: usize, z: usize) -> &mut T {
+        self.at_mut(Coord3D::new(x, y, z))
+    }
+
+    pub fn get_xyz(&self, x: usize, y: usize, z: usize) -> Option<&T> {
+        self.get(Coord3D::new(x, y, z))
+    }
+
+    pub fn get_xyz_mut(&mut self, x: usize, y: usize, z: usize) -> Option<&mut T> {
+        self.get_mut(Coord3D::new(x, y, z))
     }
 
     pub fn set<C: Into<Coord3D>>(&mut self, coord: C, value: T) {
@@ -167,6 +285,35 @@ impl<T: Clone> Array3D<T> {
         *self.at_mut(coord) = value;
     }
 
+    pub fn sub<C: Into<Coord3D>>(
+        &self,
+        coord: C,
+        width: usize,
+        height: usize,
+        depth: usize,
+    ) -> Self {
+        let coord = coord.into();
+        assert!(coord.x + width <= self.width);
+        assert!(coord.y + height <= self.height);
+        assert!(coord.z + depth <= self.depth);
+
+        let mut data: Vec<T> = Vec::with_capacity(width * height * depth);
+        for k in 0..depth {
+            for i in 0..height {
+                let src_begin = self.coord_index(Coord3D::new(coord.x, coord.y + i, coord.z + k));
+                let src_end = src_begin + width;
+                data.extend_from_slice(&self.data[src_begin..src_end]);
+            }
+        }
+
+        Self {
+            width,
+            height,
+            depth,
+            data,
+        }
+    }
+
     pub fn copy_2d<C: Into<Coord3D>>(&mut self, source: &Array2D<T>, dest: C) {
         let dest = dest.into();
         assert!(dest.x + source.width() <= self.width);
@@ -184,4 +331,273 @@ impl<T: Clone> Array3D<T> {
             dst.clone_from_slice(src);
         }
     }
+
+    pub fn copy_sub<C: Into<Coord3D>>(&mut self, source: &Array3D<T>, dest: C) {
+        let dest = dest.into();
+        assert!(dest.x + source.width <= self.width);
+        assert!(dest.y + source.height <= self.height);
+        assert!(dest.z + source.depth <= self.depth);
+
+        for k in 0..source.depth {
+            for i in 0..source.height {
+                let dst = Coord3D::new(dest.x, dest.y + i, dest.z + k);
+                let dst_begin = self.coord_index(dst);
+                let dst_end = dst_begin + source.width;
+                let src_begin = source.coord_index(Coord3D::new(0, i, k));
+                let src_end = src_begin + source.width;
+
+                let src = &source.data[src_begin..src_end];
+                let dst = &mut self.data[dst_begin..dst_end];
+                dst.clone_from_slice(src);
+            }
+        }
+    }
+
+    pub fn apply<F: FnMut(Coord3D, &mut T)>(&mut self, mut f: F) {
+        for (coord, value) in self.iter_mut() {
+            f(coord, value);
+        }
+    }
+
+    pub fn fill(&mut self, value: T) {
+        self.data.fill(value);
+    }
+
+    pub fn clear(&mut self)
+    where
+        T: Default,
+    {
+        self.data.fill(T::default());
+    }
+
+    pub fn map<U: Clone, F: FnMut(&T) -> U>(&self, f: F) -> Array3D<U> {
+        Array3D {
+            width: self.width,
+            height: self.height,
+            depth: self.depth,
+            data: self.data.iter().map(f).collect(),
+        }
+    }
+
+    pub fn map_indexed<U: Clone, F: FnMut(Coord3D, &T) -> U>(&self, f: F) -> Array3D<U> {
+        self.map_with_coord(f)
+    }
+
+    pub fn map_with_coord<U: Clone, F: FnMut(Coord3D, &T) -> U>(&self, mut f: F) -> Array3D<U> {
+        let data = self.iter().map(|(coord, value)| f(coord, value)).collect();
+        Array3D {
+            width: self.width,
+            height: self.height,
+            depth: self.depth,
+            data,
+        }
+    }
+
+    pub fn sum<S: Sum<T>>(&self) -> S {
+        self.data.iter().cloned().sum()
+    }
+
+    pub fn min_max(&self) -> Option<(T, T)>
+    where
+        T: PartialOrd,
+    {
+        let mut values = self.data.iter();
+        let first = values.next()?;
+        let (min, max) = values.fold((first, first), |(min, max), value| {
+            if value < min {
+                (value, max)
+            } else if value > max {
+                (min, value)
+            } else {
+                (min, max)
+            }
+        });
+        Some((min.clone(), max.clone()))
+    }
+
+    pub fn layer(&self, z: usize) -> Array2D<T> {
+        assert!(z < self.depth);
+        let begin = self.coord_index(Coord3D::new(0, 0, z));
+        let end = begin + self.width * self.height;
+        Array2D::from_raw(self.width, self.height, self.data[begin..end].to_vec())
+    }
+
+    pub fn rotate_layer_cw(&mut self, z: usize) {
+        assert!(self.width == self.height);
+        let mut layer = self.layer(z);
+        layer.rotate_cw_in_place();
+        self.copy_2d(&layer, Coord3D::new(0, 0, z));
+    }
+
+    pub fn rotate_layer_ccw(&mut self, z: usize) {
+        assert!(self.width == self.height);
+        let mut layer = self.layer(z);
+        layer.rotate_ccw_in_place();
+        self.copy_2d(&layer, Coord3D::new(0, 0, z));
+    }
+
+    pub fn rotate_layer_180(&mut self, z: usize) {
+        let layer = self.layer(z).rotate_90_n(2);
+        self.copy_2d(&layer, Coord3D::new(0, 0, z));
+    }
+}
+
+/// Indexes cells by anything convertible into `Coord3D`, panicking like `at` when out of
+/// bounds. There is deliberately no `Index<usize>` for linear access, so a tuple, array or
+/// `Coord3D` index always addresses a cell by its coordinates.
+impl<T: Clone, C: Into<Coord3D>> Index<C> for Array3D<T> {
+    type Output = T;
+
+    fn index(&self, coord: C) -> &T {
+        self.at(coord)
+    }
+}
+
+impl<T: Clone, C: Into<Coord3D>> IndexMut<C> for Array3D<T> {
+    fn index_mut(&mut self, coord: C) -> &mut T {
+        self.at_mut(coord)
+    }
+}
+
+impl<'a, T: Clone> IntoIterator for &'a Array3D<T> {
+    type Item = (Coord3D, &'a T);
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T: Clone> IntoIterator for &'a mut Array3D<T> {
+    type Item = (Coord3D, &'a mut T);
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T: Clone + fmt::Debug> fmt::Debug for Array3D<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let layers: Vec<Vec<&[T]>> = self
+            .data
+            .chunks((self.width * self.height).max(1))
+            .map(|layer| layer.chunks(self.width.max(1)).collect())
+            .collect();
+        f.debug_struct("Array3D")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("depth", &self.depth)
+            .field("layers", &layers)
+            .finish()
+    }
+}
+
+impl<T: Clone + Zero> Array3D<T> {
+    pub fn zeros(width: usize, height: usize, depth: usize) -> Self {
+        Self::new_with(width, height, depth, T::zero())
+    }
+}
+
+impl<T: Clone + One> Array3D<T> {
+    pub fn ones(width: usize, height: usize, depth: usize) -> Self {
+        Self::new_with(width, height, depth, T::one())
+    }
+}
+
+impl<T: Clone + Float> Array3D<T> {
+    pub fn approx_eq(&self, other: &Self, epsilon: T) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self.depth == other.depth
+            && self
+                .data
+                .iter()
+                .zip(other.data.iter())
+                .all(|(&a, &b)| (a - b).abs() <= epsilon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbered(width: usize, height: usize, depth: usize) -> Array3D<usize> {
+        let len = width * height * depth;
+        Array3D::from_data(width, height, depth, (0..len).collect()).unwrap()
+    }
+
+    #[test]
+    fn get_returns_corners() {
+        let array = numbered(4, 3, 2);
+        assert_eq!(array.get((0, 0, 0)), Some(&0));
+        assert_eq!(array.get((3, 0, 0)), Some(&3));
+        assert_eq!(array.get((0, 2, 0)), Some(&8));
+        assert_eq!(array.get((0, 0, 1)), Some(&12));
+        assert_eq!(array.get((3, 2, 1)), Some(&23));
+    }
+
+    #[test]
+    fn get_rejects_exactly_out_of_bounds() {
+        let mut array = numbered(4, 3, 2);
+        assert_eq!(array.get((4, 0, 0)), None);
+        assert_eq!(array.get((0, 3, 0)), None);
+        assert_eq!(array.get((0, 0, 2)), None);
+        assert_eq!(array.get_mut((4, 2, 1)), None);
+        assert_eq!(array.get_mut((3, 2, 2)), None);
+    }
+
+    #[test]
+    fn get_on_zero_sized() {
+        let mut array: Array3D<usize> = Array3D::new(0, 0, 0);
+        assert_eq!(array.get((0, 0, 0)), None);
+        assert_eq!(array.get_mut((0, 0, 0)), None);
+
+        let mut array: Array3D<usize> = Array3D::new(2, 2, 0);
+        assert_eq!(array.get((1, 1, 0)), None);
+        assert_eq!(array.get_mut((0, 0, 0)), None);
+    }
+
+    #[test]
+    fn get_mut_writes_cell() {
+        let mut array = numbered(4, 3, 2);
+        *array.get_mut((3, 2, 1)).unwrap() = 100;
+        assert_eq!(array.at((3, 2, 1)), &100);
+    }
+
+    #[test]
+    fn get_rejects_out_of_bounds_per_axis() {
+        let array = numbered(4, 3, 2);
+        assert_eq!(array.get((5, 0, 0)), None);
+        assert_eq!(array.get((usize::MAX, 0, 0)), None);
+        assert_eq!(array.get((0, 4, 0)), None);
+        assert_eq!(array.get((0, usize::MAX, 0)), None);
+        assert_eq!(array.get((0, 0, 3)), None);
+        assert_eq!(array.get((0, 0, usize::MAX)), None);
+    }
+
+    #[test]
+    fn get_mut_reaches_first_and_last_cell() {
+        let mut array = numbered(4, 3, 2);
+        *array.get_mut((0, 0, 0)).unwrap() += 100;
+        *array.get_mut((3, 2, 1)).unwrap() += 100;
+        assert_eq!(array.get((0, 0, 0)), Some(&100));
+        assert_eq!(array.get((3, 2, 1)), Some(&123));
+    }
+
+    #[test]
+    fn map_changes_element_type() {
+        let array = numbered(3, 2, 2);
+        let mapped = array.map(|&value| value.to_string());
+        assert_eq!((mapped.width(), mapped.height(), mapped.depth()), (3, 2, 2));
+        assert_eq!(mapped.at((2, 1, 1)), "11");
+    }
+
+    #[test]
+    fn map_with_coord_passes_coordinates() {
+        let array = numbered(3, 2, 2);
+        let mapped = array.map_with_coord(|coord, &value| (coord, value));
+        assert_eq!((mapped.width(), mapped.height(), mapped.depth()), (3, 2, 2));
+        assert_eq!(mapped.at((1, 0, 1)), &(Coord3D::new(1, 0, 1), 7));
+    }
 }
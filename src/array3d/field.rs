@@ -0,0 +1,109 @@
+use crate::{Array3D, Coord3D};
+
+impl Array3D<f32> {
+    pub fn blur(&self, radius: usize) -> Array3D<f32> {
+        let size = 2 * radius + 1;
+        let kernel = vec![1.0 / size as f32; size];
+        self.convolve_separable(&kernel)
+    }
+
+    pub fn gaussian_blur_3d(&self, sigma: f32) -> Array3D<f32> {
+        assert!(sigma > 0.0);
+
+        let radius = (3.0 * sigma).ceil() as isize;
+        let mut kernel: Vec<f32> = (-radius..=radius)
+            .map(|i| (-((i * i) as f32) / (2.0 * sigma * sigma)).exp())
+            .collect();
+        let total: f32 = kernel.iter().sum();
+        for weight in kernel.iter_mut() {
+            *weight /= total;
+        }
+        self.convolve_separable(&kernel)
+    }
+
+    /// Walks a ray from `origin` along `dir` in increments of `step`, yielding the
+    /// travelled distance and the trilinearly sampled value until the ray leaves the
+    /// volume or exceeds `max_dist`. Voxel centers lie on integer coordinates.
+    pub fn ray_march(
+        &self,
+        origin: [f32; 3],
+        dir: [f32; 3],
+        step: f32,
+        max_dist: f32,
+    ) -> impl Iterator<Item = (f32, f32)> + '_ {
+        assert!(step > 0.0);
+        let length = (dir[0] * dir[0] + dir[1] * dir[1] + dir[2] * dir[2]).sqrt();
+        assert!(length > 0.0);
+        let dir = [dir[0] / length, dir[1] / length, dir[2] / length];
+
+        (0..)
+            .map(move |i| i as f32 * step)
+            .take_while(move |&distance| distance <= max_dist)
+            .map_while(move |distance| {
+                let position = [
+                    origin[0] + dir[0] * distance,
+                    origin[1] + dir[1] * distance,
+                    origin[2] + dir[2] * distance,
+                ];
+                self.sample_trilinear(position)
+                    .map(|value| (distance, value))
+            })
+    }
+
+    fn sample_trilinear(&self, position: [f32; 3]) -> Option<f32> {
+        let extent = [self.width, self.height, self.depth];
+        let mut base = [0usize; 3];
+        let mut fraction = [0f32; 3];
+        for axis in 0..3 {
+            let p = position[axis];
+            if !(p >= 0.0 && p <= (extent[axis] as f32 - 1.0)) {
+                return None;
+            }
+            base[axis] = (p.floor() as usize).min(extent[axis].saturating_sub(2));
+            fraction[axis] = p - base[axis] as f32;
+        }
+
+        let mut value = 0.0;
+        for corner in 0..8 {
+            let mut weight = 1.0;
+            let mut coord = base;
+            for axis in 0..3 {
+                if corner & (1 << axis) != 0 {
+                    coord[axis] = (coord[axis] + 1).min(extent[axis] - 1);
+                    weight *= fraction[axis];
+                } else {
+                    weight *= 1.0 - fraction[axis];
+                }
+            }
+            if weight != 0.0 {
+                value += weight * self.at(Coord3D::new(coord[0], coord[1], coord[2]));
+            }
+        }
+        Some(value)
+    }
+
+    fn convolve_separable(&self, kernel: &[f32]) -> Array3D<f32> {
+        let x = self.convolve_axis(kernel, 0);
+        let y = x.convolve_axis(kernel, 1);
+        y.convolve_axis(kernel, 2)
+    }
+
+    fn convolve_axis(&self, kernel: &[f32], axis: usize) -> Array3D<f32> {
+        let radius = (kernel.len() / 2) as isize;
+        let extent = [self.width, self.height, self.depth][axis] as isize;
+
+        self.map_indexed(|coord, _| {
+            let position = [coord.x, coord.y, coord.z][axis] as isize;
+            kernel
+                .iter()
+                .enumerate()
+                .map(|(i, weight)| {
+                    let sample = (position + i as isize - radius).clamp(0, extent - 1) as usize;
+                    let mut source = [coord.x, coord.y, coord.z];
+                    source[axis] = sample;
+                    weight * self.at(Coord3D::new(source[0], source[1], source[2]))
+                })
+                .sum()
+        })
+    }
+}
@@ -0,0 +1,57 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::Coord2D;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LenMismatch {
+    pub expected: usize,
+    pub actual: usize,
+}
+
+impl fmt::Display for LenMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "expected {} elements, got {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl Error for LenMismatch {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DimMismatch {
+    pub expected: (usize, usize),
+    pub actual: (usize, usize),
+}
+
+impl fmt::Display for DimMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "expected dimensions {}x{}, got {}x{}",
+            self.expected.0, self.expected.1, self.actual.0, self.actual.1
+        )
+    }
+}
+
+impl Error for DimMismatch {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct OutOfBounds {
+    pub coord: Coord2D,
+}
+
+impl fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "coordinate ({}, {}) is out of bounds",
+            self.coord.x, self.coord.y
+        )
+    }
+}
+
+impl Error for OutOfBounds {}
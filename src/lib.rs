@@ -1,5 +1,21 @@
+#[cfg(feature = "bytemuck")]
+extern crate bytemuck;
+extern crate num_traits;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "serde")]
+extern crate serde;
+
 mod array2d;
 mod array3d;
+mod error;
 
-pub use array2d::Array2D;
-pub use array3d::Array3D;
+#[cfg(feature = "serde")]
+pub use array2d::serde_rle;
+pub use array2d::{
+    Array2D, ColIter, ColIterMut, Connectivity, Coord2D, CoordRange2D, CoordRangeIter, DiagIter,
+    DiagIterMut, DistanceMetric, FitMode, GridSnapshot, Iter, IterMut, MirrorAxis, MorphOp, Rect,
+    RegionIter, RegionIterMut, Zip3Iter,
+};
+pub use array3d::{Array3D, Coord3D, Iter as Iter3D, IterMut as IterMut3D};
+pub use error::{DimMismatch, LenMismatch, OutOfBounds};